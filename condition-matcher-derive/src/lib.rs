@@ -17,7 +17,7 @@
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
+use syn::{ext::IdentExt, parse_macro_input, Data, DeriveInput, Fields, Type};
 
 /// Derive macro for implementing the `Matchable` trait.
 ///
@@ -40,50 +40,214 @@ use syn::{parse_macro_input, Data, DeriveInput, Fields, Type};
 /// The macro generates:
 /// - `get_field(&self, field: &str) -> Option<&dyn Any>` - Returns a reference to any field by name
 /// - Handles `Option<T>` fields by unwrapping them when present
-#[proc_macro_derive(Matchable)]
+/// - `field_info() -> &'static [FieldInfo]` - Lists each field's name, type and optionality
+/// - `get_field_path(&self, path: &[&str]) -> Option<&dyn Any>` - Walks nested fields
+///
+/// ## Nested fields
+///
+/// Mark a field whose type is itself `Matchable` with `#[matchable(nested)]` so that
+/// paths like `["address", "city"]` recurse into it. The walk passes through
+/// `Option` and `Box`, takes an index segment for `Vec`/`VecDeque` and a key
+/// segment for `HashMap`/`BTreeMap`:
+///
+/// ```rust,ignore
+/// #[derive(Matchable, PartialEq)]
+/// struct Order {
+///     #[matchable(nested)]
+///     customer: Option<Box<Customer>>,
+///     #[matchable(nested)]
+///     items: Vec<Item>,          // "items.0.sku"
+///     tags: Vec<String>,         // "tags.0" works without the attribute
+/// }
+/// ```
+///
+/// Nested `Vec`/`VecDeque` fields also implement `get_field_elements`, so
+/// `AnyElement` and `AllElements` conditions can be evaluated against each
+/// element, e.g. "any of `items` has `qty > 1`".
+///
+/// ## Field names
+///
+/// Field attributes mirror serde's: `#[matchable(rename = "price")]` sets the
+/// public name, `#[matchable(alias = "px")]` adds another name (repeatable) and
+/// `#[matchable(skip)]` hides the field. On the struct,
+/// `#[matchable(rename_all = "camelCase")]` renames every field without an
+/// explicit `rename`; `lowercase`, `UPPERCASE`, `PascalCase`, `snake_case`,
+/// `SCREAMING_SNAKE_CASE`, `kebab-case` and `SCREAMING-KEBAB-CASE` are also accepted.
+///
+/// ## Virtual fields
+///
+/// Derived quantities can be matched like stored fields by registering a
+/// function that computes them from `&self`:
+///
+/// ```rust,ignore
+/// #[derive(Matchable, PartialEq)]
+/// #[matchable(virtual(spread = "Self::spread", mid = "mid_price"))]
+/// struct Quote {
+///     bid: f64,
+///     ask: f64,
+/// }
+///
+/// impl Quote {
+///     fn spread(&self) -> f64 {
+///         self.ask - self.bid
+///     }
+/// }
+/// ```
+///
+/// The value is computed on each lookup and returned by `get_field_value`
+/// (and `get_field_path_value` when reached through a nested field), which the
+/// evaluators use for field conditions. Virtual fields are listed in
+/// `field_info` with the type name `_`.
+///
+/// ## Tuple structs and enums
+///
+/// Tuple struct fields are named by position: `"0"`, `"1"`, ...
+///
+/// Enums expose a `variant` pseudo-field holding the active variant's name,
+/// plus the fields of that variant. Rename the pseudo-field with
+/// `#[matchable(tag = "kind")]`; `rename_all` and a variant-level `rename`
+/// change the variant names, as in serde:
+///
+/// ```rust,ignore
+/// #[derive(Matchable, PartialEq)]
+/// #[matchable(tag = "kind")]
+/// enum OrderType {
+///     Market,
+///     Limit { price: f64 },     // "kind" == "Limit", "price"
+///     Stop(f64),                // "kind" == "Stop", "0"
+/// }
+/// ```
+///
+/// A variant field named like the tag is a compile error, since the tag
+/// would hide it:
+///
+/// ```compile_fail
+/// use condition_matcher::{Matchable, MatchableDerive};
+///
+/// #[derive(MatchableDerive, PartialEq)]
+/// enum Event {
+///     Click { variant: u8 },
+/// }
+/// ```
+#[proc_macro_derive(Matchable, attributes(matchable))]
 pub fn matchable_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = &input.ident;
     let generics = &input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let field_match_arms = match &input.data {
-        Data::Struct(data) => match &data.fields {
-            Fields::Named(fields) => {
-                let arms = fields.named.iter().map(|f| {
-                    let field_name = &f.ident;
-                    let field_name_str = field_name.as_ref().unwrap().to_string();
-                    let field_type = &f.ty;
-                    
-                    // Check if the field is an Option type
-                    if is_option_type(field_type) {
-                        quote! {
-                            #field_name_str => self.#field_name.as_ref().map(|v| v as &dyn std::any::Any),
-                        }
-                    } else {
-                        quote! {
-                            #field_name_str => Some(&self.#field_name as &dyn std::any::Any),
-                        }
-                    }
-                });
-                quote! {
+    let container_attrs = match ContainerAttrs::parse(&input.attrs) {
+        Ok(attrs) => attrs,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let bodies = match &input.data {
+        Data::Struct(data) => struct_bodies(&data.fields, &container_attrs),
+        Data::Enum(data) => enum_bodies(data, &container_attrs),
+        Data::Union(_) => Ok(FieldAccess {
+            get_field: quote! { None },
+            get_field_path: quote! { None },
+            get_field_path_value: None,
+            get_field_elements: None,
+            field_is_none: None,
+            field_is_empty: None,
+            field_infos: quote! {},
+        }),
+    };
+    let FieldAccess {
+        get_field: get_field_body,
+        get_field_path: get_field_path_body,
+        get_field_path_value: get_field_path_value_body,
+        get_field_elements: get_field_elements_body,
+        field_is_none: field_is_none_body,
+        field_is_empty: field_is_empty_body,
+        field_infos,
+    } = match bodies {
+        Ok(bodies) => bodies,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // Computed fields are only reachable through `get_field_value`
+    let virtual_impl = if container_attrs.virtual_fields.is_empty() {
+        quote! {}
+    } else {
+        let arms = container_attrs.virtual_fields.iter().map(|(field_name, getter)| {
+            quote! {
+                #field_name => Some(::condition_matcher::FieldValue::Owned(Box::new(#getter(self)))),
+            }
+        });
+        quote! {
+            fn get_field_value(&self, field: &str) -> Option<::condition_matcher::FieldValue<'_>> {
+                match field {
                     #(#arms)*
+                    _ => ::condition_matcher::Matchable::get_field(self, field).map(::condition_matcher::FieldValue::Borrowed),
                 }
             }
-            Fields::Unnamed(_) => {
-                // For tuple structs, use indices
-                quote! {}
+        }
+    };
+    let virtual_infos = container_attrs.virtual_fields.iter().map(|(field_name, _)| {
+        quote! {
+            ::condition_matcher::FieldInfo {
+                name: #field_name,
+                aliases: &[],
+                type_name: "_",
+                optional: false,
+                nested: None,
+            },
+        }
+    });
+
+    // Owned path lookups only differ from the default when a nested type may compute values
+    let path_value_impl = match get_field_path_value_body {
+        Some(body) => quote! {
+            fn get_field_path_value(&self, path: &[&str]) -> Option<::condition_matcher::FieldValue<'_>> {
+                match path {
+                    [] => None,
+                    [field] => ::condition_matcher::Matchable::get_field_value(self, field),
+                    [field, rest @ ..] => #body,
+                }
             }
-            Fields::Unit => {
-                quote! {}
+        },
+        None => quote! {},
+    };
+
+    // Only nested fields can hold matchable elements
+    let elements_impl = match get_field_elements_body {
+        Some(body) => quote! {
+            fn get_field_elements(&self, path: &[&str]) -> Option<Vec<&dyn ::condition_matcher::DynMatchable>> {
+                match path {
+                    [] => None,
+                    [field, rest @ ..] => #body,
+                }
             }
         },
-        Data::Enum(_) => {
-            quote! {}
-        }
-        Data::Union(_) => {
-            quote! {}
-        }
+        None => quote! {},
+    };
+
+    // Option fields can be present without a value, which `get_field` can't express
+    let none_impl = match field_is_none_body {
+        Some(body) => quote! {
+            fn field_is_none(&self, path: &[&str]) -> Option<bool> {
+                match path {
+                    [] => None,
+                    [field, rest @ ..] => #body,
+                }
+            }
+        },
+        None => quote! {},
+    };
+
+    // Collection fields are empty by their own `is_empty`, whatever the element type
+    let empty_impl = match field_is_empty_body {
+        Some(body) => quote! {
+            fn field_is_empty(&self, path: &[&str]) -> Option<bool> {
+                match path {
+                    [] => None,
+                    [field, rest @ ..] => #body,
+                }
+            }
+        },
+        None => quote! {},
     };
 
     // Generate is_none implementation for types with Option fields
@@ -128,14 +292,28 @@ pub fn matchable_derive(input: TokenStream) -> TokenStream {
     };
 
     let expanded = quote! {
-        impl #impl_generics Matchable for #name #ty_generics #where_clause {
+        impl #impl_generics ::condition_matcher::Matchable for #name #ty_generics #where_clause {
             fn get_field(&self, field: &str) -> Option<&dyn std::any::Any> {
-                match field {
-                    #field_match_arms
-                    _ => None,
+                #get_field_body
+            }
+
+            fn get_field_path(&self, path: &[&str]) -> Option<&dyn std::any::Any> {
+                match path {
+                    [] => None,
+                    [field] => ::condition_matcher::Matchable::get_field(self, field),
+                    [field, rest @ ..] => #get_field_path_body,
                 }
             }
-            
+
+            fn field_info() -> &'static [::condition_matcher::FieldInfo] {
+                &[#field_infos #(#virtual_infos)*]
+            }
+
+            #virtual_impl
+            #path_value_impl
+            #elements_impl
+            #none_impl
+            #empty_impl
             #length_impl
             #is_none_impl
         }
@@ -153,3 +331,935 @@ fn is_option_type(ty: &Type) -> bool {
     }
     false
 }
+
+/// Get the `T` of an `Option<T>`
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+        && let syn::PathArguments::AngleBracketed(args) = &segment.arguments
+        && let Some(syn::GenericArgument::Type(inner)) = args.args.first()
+    {
+        return Some(inner);
+    }
+    None
+}
+
+/// Render a type as it would be written in source, e.g. `Vec<String>`
+fn type_to_string(ty: &Type) -> String {
+    let tokens = quote!(#ty).to_string();
+    tokens
+        .replace(" :: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+        .replace("[ ", "[")
+        .replace(" ]", "]")
+        .replace(" ;", ";")
+}
+
+/// Options set with `#[matchable(...)]` on the struct
+#[derive(Default)]
+struct ContainerAttrs {
+    /// Case convention applied to every field (or, on enums, variant) name
+    /// without an explicit `rename`
+    rename_all: Option<RenameRule>,
+    /// Name of the enum pseudo-field holding the variant name
+    tag: Option<syn::LitStr>,
+    /// Computed fields: public name and the function producing the value
+    virtual_fields: Vec<(String, syn::ExprPath)>,
+}
+
+impl ContainerAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut container = ContainerAttrs::default();
+        for attr in attrs.iter().filter(|a| a.path().is_ident("matchable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    let rule: syn::LitStr = meta.value()?.parse()?;
+                    container.rename_all = Some(
+                        RenameRule::from_str(&rule.value())
+                            .ok_or_else(|| syn::Error::new(rule.span(), "unknown rename_all rule"))?,
+                    );
+                    Ok(())
+                } else if meta.path.is_ident("tag") {
+                    container.tag = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("virtual") {
+                    meta.parse_nested_meta(|field| {
+                        let name = field
+                            .path
+                            .get_ident()
+                            .ok_or_else(|| field.error("expected a field name"))?
+                            .unraw()
+                            .to_string();
+                        let getter: syn::LitStr = field.value()?.parse()?;
+                        container.virtual_fields.push((name, getter.parse()?));
+                        Ok(())
+                    })
+                } else {
+                    Err(meta.error("unknown matchable container attribute"))
+                }
+            })?;
+        }
+        Ok(container)
+    }
+}
+
+/// Options set with `#[matchable(...)]` on a field
+#[derive(Default)]
+struct FieldAttrs {
+    /// Recurse into the field's own `get_field_path`
+    nested: bool,
+    /// Leave the field out of field access and metadata
+    skip: bool,
+    /// Public name replacing the Rust field name
+    rename: Option<String>,
+    /// Additional names the field answers to
+    aliases: Vec<String>,
+}
+
+impl FieldAttrs {
+    fn parse(field_attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut attrs = FieldAttrs::default();
+        for attr in field_attrs.iter().filter(|a| a.path().is_ident("matchable")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("nested") {
+                    attrs.nested = true;
+                } else if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                } else if meta.path.is_ident("rename") {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    attrs.rename = Some(name.value());
+                } else if meta.path.is_ident("alias") {
+                    let name: syn::LitStr = meta.value()?.parse()?;
+                    attrs.aliases.push(name.value());
+                } else {
+                    return Err(meta.error("unknown matchable attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(attrs)
+    }
+}
+
+/// Generated method bodies for `get_field`, `get_field_path` and `field_info`
+struct FieldAccess {
+    /// Body of `get_field`, with the name in `field`
+    get_field: proc_macro2::TokenStream,
+    /// Body of `get_field_path` for paths of two or more segments,
+    /// with the first segment in `field` and the remainder in `rest`
+    get_field_path: proc_macro2::TokenStream,
+    /// Same as `get_field_path` for `get_field_path_value`; `None` without nested fields
+    get_field_path_value: Option<proc_macro2::TokenStream>,
+    /// Body of `get_field_elements` with the first segment in `field` and the
+    /// remainder in `rest`; `None` without nested fields
+    get_field_elements: Option<proc_macro2::TokenStream>,
+    /// Body of `field_is_none` with the first segment in `field` and the
+    /// remainder in `rest`; `None` without `Option` or nested fields
+    field_is_none: Option<proc_macro2::TokenStream>,
+    /// Body of `field_is_empty` with the first segment in `field` and the
+    /// remainder in `rest`; `None` without fields that can be empty
+    field_is_empty: Option<proc_macro2::TokenStream>,
+    /// `FieldInfo` entries, comma-terminated
+    field_infos: proc_macro2::TokenStream,
+}
+
+/// Field access for a struct with named or tuple fields
+fn struct_bodies(fields: &Fields, container: &ContainerAttrs) -> syn::Result<FieldAccess> {
+    if let Some(tag) = &container.tag {
+        return Err(syn::Error::new(tag.span(), "`tag` is only supported on enums"));
+    }
+    let exposed = ExposedField::collect(fields, container.rename_all)?;
+    let access = |f: &ExposedField| {
+        let member = &f.member;
+        quote! { (&self.#member) }
+    };
+
+    let field_arms = field_arms(&exposed, access);
+    let value_arms = path_arms(&exposed, access, true);
+    let path_arms = path_arms(&exposed, access, false);
+    let element_arms = element_arms(&exposed, access);
+    let none_arms = none_arms(&exposed, access);
+    let empty_arms = empty_arms(&exposed, access);
+    let field_infos = field_infos(&exposed, false);
+    Ok(FieldAccess {
+        get_field: quote! {
+            match field {
+                #field_arms
+                _ => None,
+            }
+        },
+        get_field_path: quote! {
+            match *field {
+                #path_arms
+                _ => None,
+            }
+        },
+        get_field_path_value: exposed.iter().any(|f| f.attrs.nested).then(|| {
+            quote! {
+                match *field {
+                    #value_arms
+                    _ => None,
+                }
+            }
+        }),
+        get_field_elements: exposed.iter().any(|f| f.attrs.nested).then(|| {
+            quote! {
+                match *field {
+                    #element_arms
+                    _ => None,
+                }
+            }
+        }),
+        field_is_none: exposed.iter().any(ExposedField::may_be_none).then(|| {
+            quote! {
+                match *field {
+                    #none_arms
+                    _ => ::condition_matcher::Matchable::get_field_path_value(self, path).map(|v| v.is_null()),
+                }
+            }
+        }),
+        field_is_empty: exposed.iter().any(ExposedField::may_be_empty).then(|| {
+            quote! {
+                match *field {
+                    #empty_arms
+                    _ => None,
+                }
+            }
+        }),
+        field_infos,
+    })
+}
+
+/// Field access for an enum: a tag pseudo-field with the variant name, plus
+/// the fields of the active variant
+fn enum_bodies(data: &syn::DataEnum, container: &ContainerAttrs) -> syn::Result<FieldAccess> {
+    if data.variants.is_empty() {
+        return Ok(FieldAccess {
+            get_field: quote! { match *self {} },
+            get_field_path: quote! { match *self {} },
+            get_field_path_value: None,
+            get_field_elements: None,
+            field_is_none: None,
+            field_is_empty: None,
+            field_infos: quote! {},
+        });
+    }
+
+    let tag = container
+        .tag
+        .as_ref()
+        .map(|t| t.value())
+        .unwrap_or_else(|| "variant".to_string());
+
+    let mut get_field_arms = Vec::new();
+    let mut path_match_arms = Vec::new();
+    let mut value_match_arms = Vec::new();
+    let mut element_match_arms = Vec::new();
+    let mut none_match_arms = Vec::new();
+    let mut empty_match_arms = Vec::new();
+    let mut all_fields: Vec<ExposedField> = Vec::new();
+
+    for variant in &data.variants {
+        let variant_attrs = FieldAttrs::parse(&variant.attrs)?;
+        if variant_attrs.nested || variant_attrs.skip || !variant_attrs.aliases.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                "only `rename` is supported on enum variants",
+            ));
+        }
+        let ident = &variant.ident;
+        let variant_name = match (&variant_attrs.rename, container.rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rule)) => rule.apply_to_variant(&ident.unraw().to_string()),
+            (None, None) => ident.unraw().to_string(),
+        };
+
+        let exposed = ExposedField::collect(&variant.fields, None)?;
+        if let Some(clash) = exposed
+            .iter()
+            .find(|f| f.name == tag || f.attrs.aliases.contains(&tag))
+        {
+            return Err(syn::Error::new_spanned(
+                clash.field,
+                format!(
+                    "field name `{}` is taken by the variant tag; rename the field or set `#[matchable(tag = \"...\")]`",
+                    tag
+                ),
+            ));
+        }
+        let pattern = match &variant.fields {
+            Fields::Named(_) => {
+                let members = exposed.iter().map(|f| &f.member);
+                let bindings = exposed.iter().map(|f| &f.binding);
+                quote! { Self::#ident { #(#members: #bindings,)* .. } }
+            }
+            Fields::Unnamed(fields) => {
+                let positions = (0..fields.unnamed.len()).map(|i| {
+                    match exposed.iter().find(|f| f.index == i) {
+                        Some(f) => {
+                            let binding = &f.binding;
+                            quote! { #binding }
+                        }
+                        None => quote! { _ },
+                    }
+                });
+                quote! { Self::#ident(#(#positions),*) }
+            }
+            Fields::Unit => quote! { Self::#ident },
+        };
+
+        let access = |f: &ExposedField| {
+            let binding = &f.binding;
+            quote! { #binding }
+        };
+        let field_arms = field_arms(&exposed, access);
+        let value_arms = path_arms(&exposed, access, true);
+        let path_arms = path_arms(&exposed, access, false);
+        let element_arms = element_arms(&exposed, access);
+        let none_arms = none_arms(&exposed, access);
+        let empty_arms = empty_arms(&exposed, access);
+        get_field_arms.push(quote! {
+            #pattern => match field {
+                #tag => Some(&#variant_name as &dyn std::any::Any),
+                #field_arms
+                _ => None,
+            },
+        });
+        path_match_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => match *field {
+                #path_arms
+                _ => None,
+            },
+        });
+        value_match_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => match *field {
+                #value_arms
+                _ => None,
+            },
+        });
+        element_match_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => match *field {
+                #element_arms
+                _ => None,
+            },
+        });
+        none_match_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => match *field {
+                #none_arms
+                _ => ::condition_matcher::Matchable::get_field_path_value(self, path).map(|v| v.is_null()),
+            },
+        });
+        empty_match_arms.push(quote! {
+            #[allow(unused_variables)]
+            #pattern => match *field {
+                #empty_arms
+                _ => None,
+            },
+        });
+
+        for f in exposed {
+            if !all_fields.iter().any(|seen| seen.name == f.name) {
+                all_fields.push(f);
+            }
+        }
+    }
+
+    // Variant fields are only present for some values, so all are optional
+    let field_infos = field_infos(&all_fields, true);
+    Ok(FieldAccess {
+        get_field: quote! {
+            match self {
+                #(#get_field_arms)*
+            }
+        },
+        get_field_path: quote! {
+            match self {
+                #(#path_match_arms)*
+            }
+        },
+        get_field_path_value: all_fields.iter().any(|f| f.attrs.nested).then(|| {
+            quote! {
+                match self {
+                    #(#value_match_arms)*
+                }
+            }
+        }),
+        get_field_elements: all_fields.iter().any(|f| f.attrs.nested).then(|| {
+            quote! {
+                match self {
+                    #(#element_match_arms)*
+                }
+            }
+        }),
+        field_is_none: all_fields.iter().any(ExposedField::may_be_none).then(|| {
+            quote! {
+                match self {
+                    #(#none_match_arms)*
+                }
+            }
+        }),
+        field_is_empty: all_fields.iter().any(ExposedField::may_be_empty).then(|| {
+            quote! {
+                match self {
+                    #(#empty_match_arms)*
+                }
+            }
+        }),
+        field_infos: quote! {
+            ::condition_matcher::FieldInfo {
+                name: #tag,
+                aliases: &[],
+                type_name: "&'static str",
+                optional: false,
+                nested: None,
+            },
+            #field_infos
+        },
+    })
+}
+
+/// `get_field` match arms; `access` yields a `&T` expression for a field
+fn field_arms(
+    fields: &[ExposedField],
+    access: impl Fn(&ExposedField) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let arms = fields.iter().map(|f| {
+        let names = f.match_pattern();
+        let value = access(f);
+
+        // Check if the field is an Option type
+        if is_option_type(&f.field.ty) {
+            quote! {
+                #names => #value.as_ref().map(|v| v as &dyn std::any::Any),
+            }
+        } else {
+            quote! {
+                #names => Some(#value as &dyn std::any::Any),
+            }
+        }
+    });
+    quote! { #(#arms)* }
+}
+
+/// Nested path arms: the first segment picks the field, the rest is walked by `path_access`
+fn path_arms(
+    fields: &[ExposedField],
+    access: impl Fn(&ExposedField) -> proc_macro2::TokenStream,
+    owned: bool,
+) -> proc_macro2::TokenStream {
+    let arms = fields.iter().map(|f| {
+        let names = f.match_pattern();
+        let value = access(f);
+        let walk = path_access(&f.field.ty, value, quote! { rest }, f.attrs.nested, owned);
+        quote! {
+            #names => #walk,
+        }
+    });
+    quote! { #(#arms)* }
+}
+
+/// `get_field_elements` arms for nested fields; other fields have no matchable elements
+fn element_arms(
+    fields: &[ExposedField],
+    access: impl Fn(&ExposedField) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let arms = fields.iter().filter(|f| f.attrs.nested).map(|f| {
+        let names = f.match_pattern();
+        let walk = elements_access(&f.field.ty, access(f), quote! { rest });
+        quote! {
+            #names => #walk,
+        }
+    });
+    quote! { #(#arms)* }
+}
+
+/// `field_is_none` arms for fields that may hold no value or lead to nested ones
+fn none_arms(
+    fields: &[ExposedField],
+    access: impl Fn(&ExposedField) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let arms = fields.iter().filter(|f| f.may_be_none()).map(|f| {
+        let names = f.match_pattern();
+        let walk = none_access(&f.field.ty, access(f), quote! { rest }, f.attrs.nested);
+        quote! {
+            #names => #walk,
+        }
+    });
+    quote! { #(#arms)* }
+}
+
+/// `field_is_empty` arms for fields that can be empty or lead to nested ones
+fn empty_arms(
+    fields: &[ExposedField],
+    access: impl Fn(&ExposedField) -> proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let arms = fields.iter().filter(|f| f.may_be_empty()).map(|f| {
+        let names = f.match_pattern();
+        let walk = empty_access(&f.field.ty, access(f), quote! { rest }, f.attrs.nested);
+        quote! {
+            #names => #walk,
+        }
+    });
+    quote! { #(#arms)* }
+}
+
+/// Static field metadata for schemas and rule builders
+fn field_infos(fields: &[ExposedField], all_optional: bool) -> proc_macro2::TokenStream {
+    let infos = fields.iter().map(|f| {
+        let field_name_str = &f.name;
+        let aliases = &f.attrs.aliases;
+        let ty = &f.field.ty;
+        let is_option = is_option_type(ty);
+        let optional = is_option || all_optional;
+        let type_name = if is_option {
+            option_inner_type(ty).map(type_to_string).unwrap_or_default()
+        } else {
+            type_to_string(ty)
+        };
+        let nested = if f.attrs.nested {
+            let inner = innermost_type(ty);
+            quote! {
+                Some(<#inner as ::condition_matcher::Matchable>::field_info as fn() -> &'static [::condition_matcher::FieldInfo])
+            }
+        } else {
+            quote! { None }
+        };
+        quote! {
+            ::condition_matcher::FieldInfo {
+                name: #field_name_str,
+                aliases: &[#(#aliases),*],
+                type_name: #type_name,
+                optional: #optional,
+                nested: #nested,
+            },
+        }
+    });
+    quote! { #(#infos)* }
+}
+
+/// A struct or variant field exposed to conditions
+struct ExposedField<'a> {
+    field: &'a syn::Field,
+    attrs: FieldAttrs,
+    /// Public name after `rename`/`rename_all`; the index for tuple fields
+    name: String,
+    /// Position among the declared fields
+    index: usize,
+    /// How to reach the field from `self`
+    member: syn::Member,
+    /// Identifier the field is bound to in an enum variant pattern
+    binding: syn::Ident,
+}
+
+impl<'a> ExposedField<'a> {
+    fn collect(fields: &'a Fields, rename_all: Option<RenameRule>) -> syn::Result<Vec<Self>> {
+        let mut exposed = Vec::new();
+        for (index, field) in fields.iter().enumerate() {
+            let attrs = FieldAttrs::parse(&field.attrs)?;
+            if attrs.skip {
+                continue;
+            }
+            let (member, default_name) = match &field.ident {
+                Some(ident) => {
+                    let name = ident.unraw().to_string();
+                    let name = rename_all.map(|rule| rule.apply(&name)).unwrap_or(name);
+                    (syn::Member::Named(ident.clone()), name)
+                }
+                None => (syn::Member::Unnamed(index.into()), index.to_string()),
+            };
+            let name = attrs.rename.clone().unwrap_or(default_name);
+            let binding = quote::format_ident!("__field{}", index);
+            exposed.push(ExposedField {
+                field,
+                attrs,
+                name,
+                index,
+                member,
+                binding,
+            });
+        }
+        Ok(exposed)
+    }
+
+    /// Match pattern covering the public name and every alias
+    fn match_pattern(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let aliases = &self.attrs.aliases;
+        quote! { #name #(| #aliases)* }
+    }
+
+    /// Whether the field, or something below it, can be present without a value
+    fn may_be_none(&self) -> bool {
+        self.attrs.nested || contains_option(&self.field.ty)
+    }
+
+    /// Whether the field's type tells if it is empty, or it leads to nested fields
+    fn may_be_empty(&self) -> bool {
+        self.attrs.nested || has_emptiness(&self.field.ty)
+    }
+}
+
+/// Case conventions accepted by `rename_all`, named as in serde
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn from_str(rule: &str) -> Option<Self> {
+        Some(match rule {
+            "lowercase" => RenameRule::Lower,
+            "UPPERCASE" => RenameRule::Upper,
+            "PascalCase" => RenameRule::Pascal,
+            "camelCase" => RenameRule::Camel,
+            "snake_case" => RenameRule::Snake,
+            "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+            "kebab-case" => RenameRule::Kebab,
+            "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+            _ => return None,
+        })
+    }
+
+    /// Convert a snake_case field name
+    fn apply(self, field: &str) -> String {
+        match self {
+            RenameRule::Lower | RenameRule::Snake => field.to_string(),
+            RenameRule::Upper | RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+            RenameRule::Pascal | RenameRule::Camel => {
+                let mut out = String::with_capacity(field.len());
+                let mut capitalize = matches!(self, RenameRule::Pascal);
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        out.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        out.push(c);
+                    }
+                }
+                out
+            }
+            RenameRule::Kebab => field.replace('_', "-"),
+            RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+        }
+    }
+
+    /// Convert a PascalCase variant name
+    fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            RenameRule::Pascal => variant.to_string(),
+            RenameRule::Lower => variant.to_ascii_lowercase(),
+            RenameRule::Upper => variant.to_ascii_uppercase(),
+            RenameRule::Camel => {
+                let mut chars = variant.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            }
+            _ => {
+                let mut snake = String::with_capacity(variant.len() + 4);
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                self.apply(&snake)
+            }
+        }
+    }
+}
+
+/// Split a path type into its last segment name and generic type arguments
+fn type_args(ty: &Type) -> Option<(String, Vec<&Type>)> {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        let args = match &segment.arguments {
+            syn::PathArguments::AngleBracketed(args) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        return Some((segment.ident.to_string(), args));
+    }
+    None
+}
+
+/// Whether an `Option` is reached while unwrapping `Box`, sequences and maps
+fn contains_option(ty: &Type) -> bool {
+    match type_args(ty) {
+        Some((name, _)) if name == "Option" => true,
+        Some((name, args)) if matches!(name.as_str(), "Box" | "Vec" | "VecDeque") => {
+            args.first().is_some_and(|inner| contains_option(inner))
+        }
+        Some((name, args)) if matches!(name.as_str(), "HashMap" | "BTreeMap") => {
+            args.get(1).is_some_and(|inner| contains_option(inner))
+        }
+        _ => false,
+    }
+}
+
+/// Whether a value of this type can be checked for emptiness without
+/// knowing more than its type: strings, collections, maps and `Option`s
+fn has_emptiness(ty: &Type) -> bool {
+    match type_args(ty) {
+        Some((name, args)) if name == "Box" => args.first().is_some_and(|inner| has_emptiness(inner)),
+        Some((name, _)) => matches!(
+            name.as_str(),
+            "Option" | "String" | "Vec" | "VecDeque" | "HashMap" | "BTreeMap" | "HashSet" | "BTreeSet"
+        ),
+        None => false,
+    }
+}
+
+/// The element type reached after unwrapping `Option`, `Box`, sequences and maps
+fn innermost_type(ty: &Type) -> &Type {
+    match type_args(ty) {
+        Some((name, args)) if matches!(name.as_str(), "Option" | "Box" | "Vec" | "VecDeque") => {
+            args.first().map(|inner| innermost_type(inner)).unwrap_or(ty)
+        }
+        Some((name, args)) if matches!(name.as_str(), "HashMap" | "BTreeMap") => {
+            args.get(1).map(|inner| innermost_type(inner)).unwrap_or(ty)
+        }
+        _ => ty,
+    }
+}
+
+/// Expression resolving a non-empty `path` below `value` (a `&ty`) to `Option<&dyn Any>`,
+/// or to `Option<FieldValue>` when `owned` is set
+fn path_access(
+    ty: &Type,
+    value: proc_macro2::TokenStream,
+    path: proc_macro2::TokenStream,
+    nested: bool,
+    owned: bool,
+) -> proc_macro2::TokenStream {
+    match type_args(ty) {
+        Some((name, args)) if name == "Option" && args.len() == 1 => {
+            let inner = path_access(args[0], quote! { v }, path, nested, owned);
+            quote! { #value.as_ref().and_then(|v| #inner) }
+        }
+        Some((name, args)) if name == "Box" && args.len() == 1 => {
+            let inner = path_access(args[0], quote! { (&**#value) }, path, nested, owned);
+            quote! { #inner }
+        }
+        Some((name, args)) if matches!(name.as_str(), "Vec" | "VecDeque") && args.len() == 1 => {
+            let element = path_segment(args[0], quote! { v }, nested, owned);
+            quote! {{
+                let (segment, rest) = #path.split_first()?;
+                let v = #value.get(segment.parse::<usize>().ok()?)?;
+                #element
+            }}
+        }
+        Some((name, args)) if matches!(name.as_str(), "HashMap" | "BTreeMap") && args.len() == 2 => {
+            let element = path_segment(args[1], quote! { v }, nested, owned);
+            quote! {{
+                let (segment, rest) = #path.split_first()?;
+                let v = #value.get(*segment)?;
+                #element
+            }}
+        }
+        _ if nested && owned => quote! { ::condition_matcher::Matchable::get_field_path_value(#value, #path) },
+        _ if nested => quote! { ::condition_matcher::Matchable::get_field_path(#value, #path) },
+        _ => quote! { None },
+    }
+}
+
+/// Expression resolving `path` below `value` (a `&ty`) to the elements of a sequence,
+/// as `Option<Vec<&dyn DynMatchable>>`; an empty path selects `value` itself
+fn elements_access(
+    ty: &Type,
+    value: proc_macro2::TokenStream,
+    path: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match type_args(ty) {
+        Some((name, args)) if name == "Option" && args.len() == 1 => {
+            let inner = elements_access(args[0], quote! { v }, path);
+            quote! { #value.as_ref().and_then(|v| #inner) }
+        }
+        Some((name, args)) if name == "Box" && args.len() == 1 => {
+            elements_access(args[0], quote! { (&**#value) }, path)
+        }
+        Some((name, args)) if matches!(name.as_str(), "Vec" | "VecDeque") && args.len() == 1 => {
+            let inner = elements_access(args[0], quote! { v }, quote! { rest });
+            quote! {
+                if #path.is_empty() {
+                    Some(#value.iter().map(|v| v as &dyn ::condition_matcher::DynMatchable).collect())
+                } else {
+                    let (segment, rest) = #path.split_first()?;
+                    let v = #value.get(segment.parse::<usize>().ok()?)?;
+                    #inner
+                }
+            }
+        }
+        Some((name, args)) if matches!(name.as_str(), "HashMap" | "BTreeMap") && args.len() == 2 => {
+            let inner = elements_access(args[1], quote! { v }, quote! { rest });
+            quote! {{
+                let (segment, rest) = #path.split_first()?;
+                let v = #value.get(*segment)?;
+                #inner
+            }}
+        }
+        _ => quote! { ::condition_matcher::Matchable::get_field_elements(#value, #path) },
+    }
+}
+
+/// Expression checking whether `path` below `value` (a `&ty`) holds no value,
+/// as `Option<bool>`; an empty path checks `value` itself
+fn none_access(
+    ty: &Type,
+    value: proc_macro2::TokenStream,
+    path: proc_macro2::TokenStream,
+    nested: bool,
+) -> proc_macro2::TokenStream {
+    match type_args(ty) {
+        Some((name, args)) if name == "Option" && args.len() == 1 => {
+            let inner = none_access(args[0], quote! { v }, path.clone(), nested);
+            quote! {
+                if #path.is_empty() {
+                    Some(#value.is_none())
+                } else {
+                    #value.as_ref().and_then(|v| #inner)
+                }
+            }
+        }
+        Some((name, args)) if name == "Box" && args.len() == 1 => {
+            none_access(args[0], quote! { (&**#value) }, path, nested)
+        }
+        Some((name, args)) if matches!(name.as_str(), "Vec" | "VecDeque") && args.len() == 1 => {
+            let inner = none_access(args[0], quote! { v }, quote! { rest }, nested);
+            quote! {
+                if #path.is_empty() {
+                    Some(false)
+                } else {
+                    let (segment, rest) = #path.split_first()?;
+                    let v = #value.get(segment.parse::<usize>().ok()?)?;
+                    #inner
+                }
+            }
+        }
+        Some((name, args)) if matches!(name.as_str(), "HashMap" | "BTreeMap") && args.len() == 2 => {
+            let inner = none_access(args[1], quote! { v }, quote! { rest }, nested);
+            quote! {
+                if #path.is_empty() {
+                    Some(false)
+                } else {
+                    let (segment, rest) = #path.split_first()?;
+                    let v = #value.get(*segment)?;
+                    #inner
+                }
+            }
+        }
+        _ if nested => quote! { ::condition_matcher::Matchable::field_is_none(#value, #path) },
+        _ => quote! { #path.is_empty().then_some(false) },
+    }
+}
+
+/// Expression checking whether `path` below `value` (a `&ty`) is empty, as
+/// `Option<bool>`; an empty path checks `value` itself
+fn empty_access(
+    ty: &Type,
+    value: proc_macro2::TokenStream,
+    path: proc_macro2::TokenStream,
+    nested: bool,
+) -> proc_macro2::TokenStream {
+    match type_args(ty) {
+        Some((name, args)) if name == "Option" && args.len() == 1 => {
+            let inner = empty_access(args[0], quote! { v }, path.clone(), nested);
+            quote! {
+                match #value.as_ref() {
+                    None => #path.is_empty().then_some(true),
+                    Some(v) => #inner,
+                }
+            }
+        }
+        Some((name, args)) if name == "Box" && args.len() == 1 => {
+            empty_access(args[0], quote! { (&**#value) }, path, nested)
+        }
+        Some((name, args)) if matches!(name.as_str(), "Vec" | "VecDeque") && args.len() == 1 => {
+            let inner = empty_access(args[0], quote! { v }, quote! { rest }, nested);
+            quote! {
+                if #path.is_empty() {
+                    Some(#value.is_empty())
+                } else {
+                    let (segment, rest) = #path.split_first()?;
+                    let v = #value.get(segment.parse::<usize>().ok()?)?;
+                    #inner
+                }
+            }
+        }
+        Some((name, args)) if matches!(name.as_str(), "HashMap" | "BTreeMap") && args.len() == 2 => {
+            let inner = empty_access(args[1], quote! { v }, quote! { rest }, nested);
+            quote! {
+                if #path.is_empty() {
+                    Some(#value.is_empty())
+                } else {
+                    let (segment, rest) = #path.split_first()?;
+                    let v = #value.get(*segment)?;
+                    #inner
+                }
+            }
+        }
+        Some((name, _)) if matches!(name.as_str(), "String" | "HashSet" | "BTreeSet") => {
+            quote! { #path.is_empty().then(|| #value.is_empty()) }
+        }
+        _ if nested => quote! {
+            if #path.is_empty() {
+                ::condition_matcher::Matchable::is_empty(#value)
+            } else {
+                ::condition_matcher::Matchable::field_is_empty(#value, #path)
+            }
+        },
+        _ => quote! { None },
+    }
+}
+
+/// Expression for an element reached by consuming one segment, with the remainder in `rest`
+fn path_segment(
+    ty: &Type,
+    value: proc_macro2::TokenStream,
+    nested: bool,
+    owned: bool,
+) -> proc_macro2::TokenStream {
+    let leaf = if is_option_type(ty) {
+        quote! { #value.as_ref().map(|v| v as &dyn std::any::Any) }
+    } else {
+        quote! { Some(#value as &dyn std::any::Any) }
+    };
+    let leaf = if owned {
+        quote! { #leaf.map(::condition_matcher::FieldValue::Borrowed) }
+    } else {
+        leaf
+    };
+    let inner = path_access(ty, value, quote! { rest }, nested, owned);
+    quote! {
+        if rest.is_empty() {
+            #leaf
+        } else {
+            #inner
+        }
+    }
+}
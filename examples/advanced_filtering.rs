@@ -1,7 +1,7 @@
 //! Advanced filtering example demonstrating real-world use cases
 
 use condition_matcher::{
-    Condition, ConditionMode, ConditionOperator, ConditionSelector, Evaluate, JsonCondition, JsonMatcher, JsonNestedCondition, Matchable, MatchableDerive, Matcher, MatcherExt, RuleMatcher, builder::field
+    Condition, ConditionMode, ConditionOperator, ConditionSelector, Evaluate, MatchableDerive, Matcher, RuleMatcher, builder::field
 };
 
 // Complex example with multiple struct types
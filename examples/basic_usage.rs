@@ -1,7 +1,7 @@
 //! Basic usage example for condition-matcher
 
 use condition_matcher::{
-    Condition, ConditionMode, ConditionOperator, ConditionSelector, Evaluate, Matchable,
+    Condition, ConditionMode, ConditionOperator, ConditionSelector, Evaluate,
     MatchableDerive, Matcher, RuleMatcher,
 };
 
@@ -72,142 +72,56 @@ impl MetricsCache {
     }
 }
 
-/// Helper to convert Decimal to f64 for comparison.
-fn decimal_to_f64(d: &Decimal) -> f64 {
-    use rust_decimal::prelude::ToPrimitive;
-    d.to_f64().unwrap_or(0.0)
+impl Default for MetricsCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Matchable for MetricsCache {
     fn get_field(&self, _name: &str) -> Option<&dyn Any> {
         // For single field access, we don't support it directly
-        // Use get_field_path with ["asset", "field"] instead
+        // Use a path like ["asset", "field"] instead
         None
     }
 
     fn get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
-        if path.len() < 2 {
-            return None;
-        }
-
-        let asset_key = path[0];
-        let field_name = path[1];
-
-        let metrics = self.metrics.get(asset_key)?;
-
-        match field_name {
-            "asset" => Some(&metrics.asset),
-            "current_price" => Some(&metrics.current_price),
-            "current_volume" => Some(&metrics.current_volume),
-            "current_market_cap" => Some(&metrics.current_market_cap),
-            "pct_change_1h" => Some(&metrics.pct_change_1h),
-            "pct_change_4h" => Some(&metrics.pct_change_4h),
-            "pct_change_24h" => Some(&metrics.pct_change_24h),
-            "pct_change_7d" => Some(&metrics.pct_change_7d),
-            "pct_change_30d" => Some(&metrics.pct_change_30d),
-            "avg_volume_24h" => Some(&metrics.avg_volume_24h),
-            "volume_multiplier" => Some(&metrics.volume_multiplier),
-            "volatility_24h" => Some(&metrics.volatility_24h),
-            "sma_7d" => Some(&metrics.sma_7d),
-            "sma_30d" => Some(&metrics.sma_30d),
-            "sma_50d" => Some(&metrics.sma_50d),
-            "sma_200d" => Some(&metrics.sma_200d),
-            "above_sma_7d" => Some(&metrics.above_sma_7d),
-            "above_sma_50d" => Some(&metrics.above_sma_50d),
-            "above_sma_200d" => Some(&metrics.above_sma_200d),
+        match path {
+            [asset_key, field_name] => self.metrics.get(*asset_key)?.get_field(field_name),
             _ => None,
         }
     }
 }
 
 /// Implement Matchable for individual AssetMetrics for direct matching.
+///
+/// With the `decimal` feature, Decimal fields are compared exactly against
+/// JSON numbers, so they can be returned as they are.
 impl Matchable for AssetMetrics {
     fn get_field(&self, name: &str) -> Option<&dyn Any> {
         match name {
             "asset" => Some(&self.asset),
             "current_price" => Some(&self.current_price),
-            "current_volume" => Some(&self.current_volume),
-            "current_market_cap" => Some(&self.current_market_cap),
-            "pct_change_1h" => Some(&self.pct_change_1h),
-            "pct_change_4h" => Some(&self.pct_change_4h),
-            "pct_change_24h" => Some(&self.pct_change_24h),
-            "pct_change_7d" => Some(&self.pct_change_7d),
-            "pct_change_30d" => Some(&self.pct_change_30d),
-            "avg_volume_24h" => Some(&self.avg_volume_24h),
-            "volume_multiplier" => Some(&self.volume_multiplier),
-            "volatility_24h" => Some(&self.volatility_24h),
-            "sma_7d" => Some(&self.sma_7d),
-            "sma_30d" => Some(&self.sma_30d),
-            "sma_50d" => Some(&self.sma_50d),
-            "sma_200d" => Some(&self.sma_200d),
-            "above_sma_7d" => Some(&self.above_sma_7d),
-            "above_sma_50d" => Some(&self.above_sma_50d),
-            "above_sma_200d" => Some(&self.above_sma_200d),
+            "current_volume" => self.current_volume.as_ref().map(|v| v as &dyn Any),
+            "current_market_cap" => self.current_market_cap.as_ref().map(|v| v as &dyn Any),
+            "pct_change_1h" => self.pct_change_1h.as_ref().map(|v| v as &dyn Any),
+            "pct_change_4h" => self.pct_change_4h.as_ref().map(|v| v as &dyn Any),
+            "pct_change_24h" => self.pct_change_24h.as_ref().map(|v| v as &dyn Any),
+            "pct_change_7d" => self.pct_change_7d.as_ref().map(|v| v as &dyn Any),
+            "pct_change_30d" => self.pct_change_30d.as_ref().map(|v| v as &dyn Any),
+            "avg_volume_24h" => self.avg_volume_24h.as_ref().map(|v| v as &dyn Any),
+            "volume_multiplier" => self.volume_multiplier.as_ref().map(|v| v as &dyn Any),
+            "volatility_24h" => self.volatility_24h.as_ref().map(|v| v as &dyn Any),
+            "sma_7d" => self.sma_7d.as_ref().map(|v| v as &dyn Any),
+            "sma_30d" => self.sma_30d.as_ref().map(|v| v as &dyn Any),
+            "sma_50d" => self.sma_50d.as_ref().map(|v| v as &dyn Any),
+            "sma_200d" => self.sma_200d.as_ref().map(|v| v as &dyn Any),
+            "above_sma_7d" => self.above_sma_7d.as_ref().map(|v| v as &dyn Any),
+            "above_sma_50d" => self.above_sma_50d.as_ref().map(|v| v as &dyn Any),
+            "above_sma_200d" => self.above_sma_200d.as_ref().map(|v| v as &dyn Any),
             _ => None,
         }
     }
-
-    fn get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
-        if path.len() == 1 {
-            self.get_field(path[0])
-        } else {
-            None
-        }
-    }
-}
-
-/// Wrapper to make AssetMetrics comparable with f64 values in JSON conditions.
-/// This wraps numeric fields as f64 for comparison.
-#[derive(Debug, PartialEq)]
-pub struct AssetMetricsView<'a> {
-    metrics: &'a AssetMetrics,
-    // Cache converted values for the lifetime of the view
-    current_price_f64: f64,
-    pct_change_1h_f64: Option<f64>,
-    pct_change_24h_f64: Option<f64>,
-    pct_change_7d_f64: Option<f64>,
-    volume_multiplier_f64: Option<f64>,
-    volatility_24h_f64: Option<f64>,
-}
-
-impl<'a> AssetMetricsView<'a> {
-    pub fn new(metrics: &'a AssetMetrics) -> Self {
-        Self {
-            metrics,
-            current_price_f64: decimal_to_f64(&metrics.current_price),
-            pct_change_1h_f64: metrics.pct_change_1h.as_ref().map(decimal_to_f64),
-            pct_change_24h_f64: metrics.pct_change_24h.as_ref().map(decimal_to_f64),
-            pct_change_7d_f64: metrics.pct_change_7d.as_ref().map(decimal_to_f64),
-            volume_multiplier_f64: metrics.volume_multiplier.as_ref().map(decimal_to_f64),
-            volatility_24h_f64: metrics.volatility_24h.as_ref().map(decimal_to_f64),
-        }
-    }
-}
-
-impl Matchable for AssetMetricsView<'_> {
-    fn get_field(&self, name: &str) -> Option<&dyn Any> {
-        match name {
-            "asset" => Some(&self.metrics.asset),
-            "current_price" => Some(&self.current_price_f64),
-            "pct_change_1h" => self.pct_change_1h_f64.as_ref().map(|v| v as &dyn Any),
-            "pct_change_24h" => self.pct_change_24h_f64.as_ref().map(|v| v as &dyn Any),
-            "pct_change_7d" => self.pct_change_7d_f64.as_ref().map(|v| v as &dyn Any),
-            "volume_multiplier" => self.volume_multiplier_f64.as_ref().map(|v| v as &dyn Any),
-            "volatility_24h" => self.volatility_24h_f64.as_ref().map(|v| v as &dyn Any),
-            "above_sma_7d" => self.metrics.above_sma_7d.as_ref().map(|v| v as &dyn Any),
-            "above_sma_50d" => self.metrics.above_sma_50d.as_ref().map(|v| v as &dyn Any),
-            "above_sma_200d" => self.metrics.above_sma_200d.as_ref().map(|v| v as &dyn Any),
-            _ => None,
-        }
-    }
-
-    fn get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
-        if path.len() == 1 {
-            self.get_field(path[0])
-        } else {
-            None
-        }
-    }
 }
 
 fn main() {
@@ -288,18 +202,14 @@ fn main() {
         computed_at: now,
     };
 
-    // Create views for matching (converts Decimal to f64)
-    let assets = vec![
-        AssetMetricsView::new(&btc),
-        AssetMetricsView::new(&eth),
-        AssetMetricsView::new(&sol),
-    ];
-
     println!("Sample assets:");
     println!("  BTC: ${}, 24h change: {}%", btc.current_price, btc.pct_change_24h.unwrap());
     println!("  ETH: ${}, 24h change: {}%", eth.current_price, eth.pct_change_24h.unwrap());
     println!("  SOL: ${}, 24h change: {}%", sol.current_price, sol.pct_change_24h.unwrap());
 
+    // AssetMetrics implements Matchable, so it can be matched directly
+    let assets = vec![btc, eth, sol];
+
     // Example 1: Find assets with positive 24h change
     println!("\n--- Example 1: Assets with positive 24h change ---");
     let condition = r#"{
@@ -312,7 +222,7 @@ fn main() {
     let matches: Vec<_> = assets.iter().filter(|a| matcher.matches(*a)).collect();
     println!("Found {} assets with positive 24h change:", matches.len());
     for m in &matches {
-        println!("  - {} ({}%)", m.metrics.asset, m.metrics.pct_change_24h.unwrap());
+        println!("  - {} ({}%)", m.asset, m.pct_change_24h.unwrap());
     }
 
     // Example 2: Find assets pumping (high 24h change + high volume)
@@ -330,9 +240,9 @@ fn main() {
     for m in &matches {
         println!(
             "  - {} (24h: {}%, vol mult: {}x)",
-            m.metrics.asset,
-            m.metrics.pct_change_24h.unwrap(),
-            m.metrics.volume_multiplier.unwrap()
+            m.asset,
+            m.pct_change_24h.unwrap(),
+            m.volume_multiplier.unwrap()
         );
     }
 
@@ -350,7 +260,7 @@ fn main() {
     let matches: Vec<_> = assets.iter().filter(|a| matcher.matches(*a)).collect();
     println!("Found {} bullish assets (above all SMAs):", matches.len());
     for m in &matches {
-        println!("  - {}", m.metrics.asset);
+        println!("  - {}", m.asset);
     }
 
     // Example 4: Complex nested conditions - either pumping OR (dipping but above 200 SMA)
@@ -377,12 +287,12 @@ fn main() {
     let matches: Vec<_> = assets.iter().filter(|a| matcher.matches(*a)).collect();
     println!("Found {} assets (pumping OR dipping but bullish):", matches.len());
     for m in &matches {
-        let reason = if decimal_to_f64(&m.metrics.pct_change_24h.unwrap()) > 5.0 {
+        let reason = if m.pct_change_24h.unwrap() > dec!(5) {
             "pumping"
         } else {
             "dipping but above 200 SMA"
         };
-        println!("  - {} ({})", m.metrics.asset, reason);
+        println!("  - {} ({})", m.asset, reason);
     }
 
     // Example 5: Using filter extension method
@@ -397,9 +307,29 @@ fn main() {
     let expensive_assets = matcher.filter(&assets);
     println!("Assets with price > $1000: {}", expensive_assets.len());
     for a in expensive_assets {
-        println!("  - {}: ${}", a.metrics.asset, a.metrics.current_price);
+        println!("  - {}: ${}", a.asset, a.current_price);
     }
 
+    // Example 6: Query a specific asset in a cache using "ASSET.field" paths
+    println!("\n--- Example 6: Cross-asset condition on a metrics cache ---");
+    let mut cache = MetricsCache::new();
+    for asset in assets {
+        cache.insert(asset);
+    }
+    let condition = r#"{
+        "mode": "AND",
+        "rules": [
+            {"field": "BTC.pct_change_24h", "operator": "greater_than", "value": 0},
+            {"field": "SOL.volume_multiplier", "operator": "greater_than", "value": 1.2}
+        ]
+    }"#;
+    let matcher = JsonMatcher::from_json(condition).unwrap();
+    println!(
+        "BTC up and SOL volume elevated: {} (SOL price ${})",
+        matcher.matches(&cache),
+        cache.get("SOL").unwrap().current_price
+    );
+
     println!("\n=== Example Complete ===");
 }
 
@@ -63,6 +63,16 @@ impl MetricsCache {
     pub fn len(&self) -> usize {
         self.metrics.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+}
+
+impl Default for MetricsCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Matchable for MetricsCache {
@@ -141,12 +151,19 @@ fn generate_cache(count: usize, rng: &mut StdRng) -> (MetricsCache, Vec<String>)
     (cache, asset_ids)
 }
 
+/// Template producing a watcher condition for one asset.
+type SingleAssetTemplate = Box<dyn Fn(&str, &mut StdRng) -> String>;
+/// Template producing a watcher condition spanning two assets.
+type TwoAssetTemplate = Box<dyn Fn(&str, &str, &mut StdRng) -> String>;
+/// Template producing a watcher condition spanning three assets.
+type ThreeAssetTemplate = Box<dyn Fn(&str, &str, &str, &mut StdRng) -> String>;
+
 /// Generate diverse watcher conditions that target specific assets.
 /// Each watcher uses "ASSET_ID.field" notation in its conditions.
 /// These are complex matchers with multiple rules and nested conditions.
 fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Vec<Watcher> {
     // Complex condition templates with multiple rules and nested conditions
-    let condition_templates: Vec<Box<dyn Fn(&str, &mut StdRng) -> String>> = vec![
+    let condition_templates: Vec<SingleAssetTemplate> = vec![
         // 1. Bullish momentum: price up + volume spike + above short-term SMA
         Box::new(|asset_id: &str, rng: &mut StdRng| {
             let pct_24h = rng.gen_range(5.0..20.0);
@@ -302,8 +319,7 @@ fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Ve
                                 "mode": "AND",
                                 "rules": [
                                     {{"field": "{}.volatility_24h", "operator": "less_than", "value": {}}},
-                                    {{"field": "{}.pct_change_24h", "operator": "greater_than", "value": -2.0}},
-                                    {{"field": "{}.pct_change_24h", "operator": "less_than", "value": 5.0}}
+                                    {{"field": "{}.pct_change_24h", "operator": "between", "value": {{"min": -2.0, "max": 5.0, "inclusive": false}}}}
                                 ]
                             }}
                         ]
@@ -312,7 +328,7 @@ fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Ve
             }}"#,
                 asset_id, asset_id, asset_id,
                 asset_id, high_vol, asset_id, pump_pct,
-                asset_id, low_vol, asset_id, asset_id
+                asset_id, low_vol, asset_id
             )
         }),
         // 7. Capitulation signal: sharp drop + extreme volume + below all SMAs
@@ -349,8 +365,7 @@ fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Ve
                     {{
                         "mode": "AND",
                         "rules": [
-                            {{"field": "{}.current_price", "operator": "greater_than", "value": {}}},
-                            {{"field": "{}.current_price", "operator": "less_than", "value": {}}},
+                            {{"field": "{}.current_price", "operator": "between", "value": {{"min": {}, "max": {}, "inclusive": false}}}},
                             {{"field": "{}.above_sma_7d", "operator": "equals", "value": true}},
                             {{"field": "{}.pct_change_24h", "operator": "greater_than", "value": 0}}
                         ]
@@ -365,7 +380,7 @@ fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Ve
                     }}
                 ]
             }}"#,
-                asset_id, min_price, asset_id, max_price, asset_id, asset_id,
+                asset_id, min_price, max_price, asset_id, asset_id,
                 asset_id, max_price, asset_id, breakout_pct, asset_id
             )
         }),
@@ -424,7 +439,7 @@ fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Ve
     ];
 
     // 2-asset condition templates
-    let two_asset_templates: Vec<Box<dyn Fn(&str, &str, &mut StdRng) -> String>> = vec![
+    let two_asset_templates: Vec<TwoAssetTemplate> = vec![
         // 11. Correlation check: both assets pumping together
         Box::new(|a1: &str, a2: &str, rng: &mut StdRng| {
             let pct1 = rng.gen_range(5.0..15.0);
@@ -548,7 +563,7 @@ fn generate_watchers(count: usize, asset_ids: &[String], rng: &mut StdRng) -> Ve
     ];
 
     // 3-asset condition templates
-    let three_asset_templates: Vec<Box<dyn Fn(&str, &str, &str, &mut StdRng) -> String>> = vec![
+    let three_asset_templates: Vec<ThreeAssetTemplate> = vec![
         // 16. Sector momentum: all three assets showing strength
         Box::new(|a1: &str, a2: &str, a3: &str, rng: &mut StdRng| {
             let pct = rng.gen_range(3.0..10.0);
@@ -847,7 +862,7 @@ fn main() {
     // Find most referenced asset in triggered watchers
     if let Some((asset_id, count)) = triggers_by_asset.iter().max_by_key(|(_, c)| *c) {
         println!("\n  Most referenced asset in triggered watchers: {} ({} times)", asset_id, count);
-        if let Some(metrics) = cache.get(*asset_id) {
+        if let Some(metrics) = cache.get(asset_id) {
             println!(
                 "    Price: ${:.2}, 24h: {:.1}%, 7d: {:.1}%, Vol mult: {:.2}x",
                 metrics.current_price,
@@ -5,6 +5,8 @@ use crate::{
     matchable::Matchable,
     matchers::RuleMatcher,
 };
+#[cfg(feature = "json_condition")]
+use crate::condition::JsonNestedCondition;
 use std::any::Any;
 
 // ============================================================================
@@ -143,7 +145,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field equals value
-    pub fn equals(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn equals(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::Equals,
@@ -151,7 +153,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field not equals value
-    pub fn not_equals(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn not_equals(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::NotEquals,
@@ -159,7 +161,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field greater than value
-    pub fn gt(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn gt(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::GreaterThan,
@@ -167,7 +169,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field greater than or equal value
-    pub fn gte(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn gte(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::GreaterThanOrEqual,
@@ -175,7 +177,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field less than value
-    pub fn lt(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn lt(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::LessThan,
@@ -183,7 +185,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field less than or equal value
-    pub fn lte(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn lte(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::LessThanOrEqual,
@@ -191,7 +193,7 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field contains substring (for string fields)
-    pub fn contains(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn contains(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::Contains,
@@ -199,20 +201,124 @@ impl<'a, T: Matchable> FieldConditionBuilder<'a, T> {
     }
 
     /// Field starts with prefix (for string fields)
-    pub fn starts_with(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn starts_with(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::StartsWith,
         }
     }
 
+    /// Field is one of the values in a `Vec`, `&'static` slice or `HashSet`
+    pub fn is_in(self, values: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, values),
+            operator: ConditionOperator::In,
+        }
+    }
+
+    /// Field is none of the values in a `Vec`, `&'static` slice or `HashSet`
+    pub fn not_in(self, values: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, values),
+            operator: ConditionOperator::NotIn,
+        }
+    }
+
+    /// Field lies within a `RangeInclusive`, `Range` or `(Bound, Bound)`
+    pub fn between(self, range: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, range),
+            operator: ConditionOperator::Between,
+        }
+    }
+
     /// Field ends with suffix (for string fields)
-    pub fn ends_with(self, value: &'a dyn Any) -> Condition<'a, T> {
+    pub fn ends_with(self, value: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
         Condition {
             selector: ConditionSelector::FieldValue(self.field, value),
             operator: ConditionOperator::EndsWith,
         }
     }
+
+    /// Compare the field with another field of the same value, e.g.
+    /// `field("bid_price").compare_field(ConditionOperator::LessThan, "ask_price")`
+    pub fn compare_field(self, operator: ConditionOperator, other: &'a str) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldToField(self.field, other),
+            operator,
+        }
+    }
+
+    /// Option field is None; a missing field is an error
+    pub fn is_none(self) -> Condition<'a, T> {
+        self.unary(ConditionOperator::IsNone)
+    }
+
+    /// Option field is Some; a missing field is an error
+    pub fn is_some(self) -> Condition<'a, T> {
+        self.unary(ConditionOperator::IsSome)
+    }
+
+    /// Field path resolves, even if the field is None
+    pub fn exists(self) -> Condition<'a, T> {
+        self.unary(ConditionOperator::Exists)
+    }
+
+    /// Field path doesn't resolve
+    pub fn not_exists(self) -> Condition<'a, T> {
+        self.unary(ConditionOperator::NotExists)
+    }
+
+    /// String, collection or map field is empty, or the field is None
+    pub fn is_empty(self) -> Condition<'a, T> {
+        self.unary(ConditionOperator::IsEmpty)
+    }
+
+    /// String, collection or map field has content
+    pub fn is_not_empty(self) -> Condition<'a, T> {
+        self.unary(ConditionOperator::IsNotEmpty)
+    }
+
+    fn unary(self, operator: ConditionOperator) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, &()),
+            operator,
+        }
+    }
+
+    /// Collection field contains at least one of the items in a `Vec`, `&'static` slice or `HashSet`
+    pub fn contains_any(self, values: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, values),
+            operator: ConditionOperator::ContainsAny,
+        }
+    }
+
+    /// Collection field contains every item in a `Vec`, `&'static` slice or `HashSet`
+    pub fn contains_all(self, values: &'a (dyn Any + Send + Sync)) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, values),
+            operator: ConditionOperator::ContainsAll,
+        }
+    }
+
+    /// At least one element of the collection field matches the condition group
+    #[cfg(feature = "json_condition")]
+    pub fn any_element(self, condition: &'a JsonNestedCondition) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, condition),
+            operator: ConditionOperator::AnyElement,
+        }
+    }
+
+    /// Every element of the collection field matches the condition group
+    #[cfg(feature = "json_condition")]
+    pub fn all_elements(self, condition: &'a JsonNestedCondition) -> Condition<'a, T> {
+        Condition {
+            selector: ConditionSelector::FieldValue(self.field, condition),
+            operator: ConditionOperator::AllElements,
+        }
+    }
 }
 
 /// Convenience function to create a field condition builder
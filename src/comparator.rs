@@ -0,0 +1,267 @@
+//! Registry of comparators for types the evaluators don't know natively.
+//!
+//! Values are compared as `&dyn Any`, so only the built-in primitive and
+//! string types can be compared out of the box. Registering a [`Comparator`]
+//! for a type lets fields of that type take part in `Equals`, `GreaterThan`
+//! and the other comparison operators, in both programmatic and JSON
+//! conditions.
+//!
+//! # Example
+//!
+//! ```rust
+//! use condition_matcher::{field, Comparator, Matcher, MatchableDerive, Matchable, RuleMatcher};
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+//! struct Cents(i64);
+//!
+//! Comparator::<Cents>::partial_ord()
+//!     .with_display(|c| format!("{}.{:02}", c.0 / 100, c.0 % 100))
+//!     .register();
+//!
+//! #[derive(MatchableDerive, PartialEq)]
+//! struct Order {
+//!     total: Cents,
+//! }
+//!
+//! let minimum = Cents(10_00);
+//! let mut matcher = RuleMatcher::and();
+//! matcher.add_condition(field::<Order>("total").gte(&minimum));
+//! assert!(matcher.matches(&Order { total: Cents(12_50) }));
+//! ```
+
+use std::any::{Any, TypeId};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::condition::ConditionOperator;
+
+type OrderingFn = Box<dyn Fn(&dyn Any, &dyn Any) -> Option<Ordering> + Send + Sync>;
+type EqualityFn = Box<dyn Fn(&dyn Any, &dyn Any) -> Option<bool> + Send + Sync>;
+type DisplayFn = Box<dyn Fn(&dyn Any) -> Option<String> + Send + Sync>;
+#[cfg(feature = "json_condition")]
+type FromJsonFn = Box<dyn Fn(&serde_json::Value) -> Option<Box<dyn Any>> + Send + Sync>;
+
+/// How to compare values of a custom type `T`.
+///
+/// Build one with [`Comparator::new`] or [`Comparator::partial_ord`], then call
+/// [`register`](Comparator::register) to make it available to every matcher.
+pub struct Comparator<T> {
+    ordering: Option<OrderingFn>,
+    equality: Option<EqualityFn>,
+    display: Option<DisplayFn>,
+    #[cfg(feature = "json_condition")]
+    from_json: Option<FromJsonFn>,
+    _type: std::marker::PhantomData<fn(&T)>,
+}
+
+impl<T: Any + Send + Sync> Comparator<T> {
+    /// Create a comparator that supports no operators yet.
+    pub fn new() -> Self {
+        Self {
+            ordering: None,
+            equality: None,
+            display: None,
+            #[cfg(feature = "json_condition")]
+            from_json: None,
+            _type: std::marker::PhantomData,
+        }
+    }
+
+    /// Create a comparator using the type's `PartialOrd` and `PartialEq` impls.
+    pub fn partial_ord() -> Self
+    where
+        T: PartialOrd,
+    {
+        Self::new()
+            .with_ordering(|a: &T, b: &T| a.partial_cmp(b))
+            .with_equality(|a: &T, b: &T| a == b)
+    }
+
+    /// Set the ordering used by `GreaterThan`, `LessThan` and friends.
+    ///
+    /// Also used for `Equals`/`NotEquals` when no equality is set.
+    pub fn with_ordering(
+        mut self,
+        ordering: impl Fn(&T, &T) -> Option<Ordering> + Send + Sync + 'static,
+    ) -> Self {
+        self.ordering = Some(Box::new(move |a, b| {
+            ordering(a.downcast_ref()?, b.downcast_ref()?)
+        }));
+        self
+    }
+
+    /// Set the equality used by `Equals` and `NotEquals`.
+    pub fn with_equality(
+        mut self,
+        equality: impl Fn(&T, &T) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.equality = Some(Box::new(move |a, b| {
+            Some(equality(a.downcast_ref()?, b.downcast_ref()?))
+        }));
+        self
+    }
+
+    /// Set how values are rendered in results. Without it, values are shown
+    /// as the type name.
+    pub fn with_display(mut self, display: impl Fn(&T) -> String + Send + Sync + 'static) -> Self {
+        self.display = Some(Box::new(move |v| Some(display(v.downcast_ref()?))));
+        self
+    }
+
+    /// Set how JSON operands are converted to `T`, enabling JSON conditions on
+    /// fields of this type.
+    #[cfg(feature = "json_condition")]
+    pub fn with_json(
+        mut self,
+        from_json: impl Fn(&serde_json::Value) -> Option<T> + Send + Sync + 'static,
+    ) -> Self {
+        self.from_json = Some(Box::new(move |v| {
+            from_json(v).map(|t| Box::new(t) as Box<dyn Any>)
+        }));
+        self
+    }
+
+    /// Register this comparator, replacing any previous one for `T`.
+    pub fn register(self) {
+        register_comparator(self);
+    }
+}
+
+impl<T: Any + Send + Sync> Default for Comparator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Type-erased form of a [`Comparator`], as stored in the registry.
+struct ErasedComparator {
+    type_name: &'static str,
+    ordering: Option<OrderingFn>,
+    equality: Option<EqualityFn>,
+    display: Option<DisplayFn>,
+    #[cfg(feature = "json_condition")]
+    from_json: Option<FromJsonFn>,
+}
+
+impl ErasedComparator {
+    fn compare(
+        &self,
+        actual: &dyn Any,
+        expected: &dyn Any,
+        operator: &ConditionOperator,
+    ) -> Option<(bool, Option<String>, Option<String>)> {
+        let ordering = || self.ordering.as_ref().and_then(|f| f(actual, expected));
+        let equal = || {
+            self.equality
+                .as_ref()
+                .and_then(|f| f(actual, expected))
+                .or_else(|| ordering().map(|o| o == Ordering::Equal))
+        };
+
+        let passed = match operator {
+            ConditionOperator::Equals => equal()?,
+            ConditionOperator::NotEquals => !equal()?,
+            ConditionOperator::GreaterThan => ordering()? == Ordering::Greater,
+            ConditionOperator::LessThan => ordering()? == Ordering::Less,
+            ConditionOperator::GreaterThanOrEqual => ordering()? != Ordering::Less,
+            ConditionOperator::LessThanOrEqual => ordering()? != Ordering::Greater,
+            _ => return None,
+        };
+        Some((passed, self.display(actual), self.display(expected)))
+    }
+
+    /// Render a value with the registered display, or as its type name.
+    fn display(&self, value: &dyn Any) -> Option<String> {
+        self.display
+            .as_ref()
+            .and_then(|f| f(value))
+            .or_else(|| Some(format!("<{}>", self.type_name)))
+    }
+}
+
+/// Whether any comparator is registered, so that comparisons of built-in
+/// types skip the registry lock while it's empty.
+static ANY_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+fn registry() -> &'static RwLock<HashMap<TypeId, Arc<ErasedComparator>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<TypeId, Arc<ErasedComparator>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register a comparator for `T`, replacing any previous one.
+pub fn register_comparator<T: Any + Send + Sync>(comparator: Comparator<T>) {
+    let erased = ErasedComparator {
+        type_name: std::any::type_name::<T>(),
+        ordering: comparator.ordering,
+        equality: comparator.equality,
+        display: comparator.display,
+        #[cfg(feature = "json_condition")]
+        from_json: comparator.from_json,
+    };
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.insert(TypeId::of::<T>(), Arc::new(erased));
+    ANY_REGISTERED.store(true, AtomicOrdering::Release);
+}
+
+/// Remove the comparator registered for `T`, if any.
+pub fn unregister_comparator<T: Any>() {
+    let mut registry = registry().write().unwrap_or_else(|e| e.into_inner());
+    registry.remove(&TypeId::of::<T>());
+    ANY_REGISTERED.store(!registry.is_empty(), AtomicOrdering::Release);
+}
+
+fn lookup(type_id: TypeId) -> Option<Arc<ErasedComparator>> {
+    if !ANY_REGISTERED.load(AtomicOrdering::Acquire) {
+        return None;
+    }
+    registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&type_id)
+        .cloned()
+}
+
+/// Compare two values of the same registered type.
+/// Returns `None` if no comparator applies.
+pub(crate) fn compare_custom(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    if actual.type_id() != expected.type_id() {
+        return None;
+    }
+    lookup(actual.type_id())?.compare(actual, expected, operator)
+}
+
+/// Compare a value of a registered type against a JSON operand, converting
+/// the operand with the comparator's JSON conversion.
+/// Returns `None` if no comparator applies.
+#[cfg(feature = "json_condition")]
+pub(crate) fn compare_custom_json(
+    actual: &dyn Any,
+    expected: &serde_json::Value,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    let comparator = lookup(actual.type_id())?;
+    let converted = comparator.from_json.as_ref()?(expected)?;
+    let (passed, actual_str, _) = comparator.compare(actual, converted.as_ref(), operator)?;
+    Some((passed, actual_str, Some(expected.to_string())))
+}
+
+/// Compare a JSON document value against an expected value of a registered
+/// type, converting the document value with the comparator's JSON conversion.
+/// Returns `None` if no comparator applies.
+#[cfg(feature = "json_condition")]
+pub(crate) fn compare_json_to_custom(
+    actual: &serde_json::Value,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    let comparator = lookup(expected.type_id())?;
+    let converted = comparator.from_json.as_ref()?(actual)?;
+    let (passed, _, expected_str) = comparator.compare(converted.as_ref(), expected, operator)?;
+    Some((passed, Some(actual.to_string()), expected_str))
+}
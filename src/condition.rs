@@ -1,7 +1,14 @@
 use std::any::Any;
+use std::borrow::Cow;
 
+#[cfg(feature = "json_condition")]
+use crate::expression::Expression;
 use crate::{
-    evaluators::{FieldEvaluator, LengthEvaluator, PathEvaluator, TypeEvaluator, ValueEvaluator},
+    error::MatchError,
+    evaluators::{
+        CompareOptions, FieldEvaluator, LengthEvaluator, PathEvaluator, TypeEvaluator,
+        ValueEvaluator,
+    },
     matchable::Matchable,
     result::ConditionResult,
     traits::Predicate,
@@ -47,6 +54,12 @@ pub enum ConditionOperator {
     EndsWith,
     /// Value matches regex pattern
     Regex,
+    /// Value is one of a set of values
+    In,
+    /// Value is not one of a set of values
+    NotIn,
+    /// Value lies within a range
+    Between,
     /// Check if value is None/null
     IsNone,
     /// Check if value is Some/present
@@ -55,10 +68,182 @@ pub enum ConditionOperator {
     IsEmpty,
     /// Check if collection is not empty
     IsNotEmpty,
+    /// Collection contains at least one of a set of values
+    ContainsAny,
+    /// Collection contains every one of a set of values
+    ContainsAll,
+    /// At least one element of a collection matches a condition group
+    AnyElement,
+    /// Every element of a collection matches a condition group
+    AllElements,
+    /// The field path resolves, even if the field holds None/null
+    Exists,
+    /// The field path doesn't resolve
+    NotExists,
+}
+
+impl ConditionOperator {
+    /// Whether this operator only inspects the field and takes no operand.
+    pub fn is_unary(&self) -> bool {
+        matches!(
+            self,
+            ConditionOperator::IsNone
+                | ConditionOperator::IsSome
+                | ConditionOperator::IsEmpty
+                | ConditionOperator::IsNotEmpty
+                | ConditionOperator::Exists
+                | ConditionOperator::NotExists
+        )
+    }
+
+    /// Whether this operator checks whether a field is empty, rather than
+    /// comparing it with an operand.
+    pub fn is_emptiness(&self) -> bool {
+        matches!(self, ConditionOperator::IsEmpty | ConditionOperator::IsNotEmpty)
+    }
+
+    /// Whether this operator checks that a field is present or holds a
+    /// value, rather than comparing the value.
+    pub fn is_presence(&self) -> bool {
+        matches!(
+            self,
+            ConditionOperator::IsNone
+                | ConditionOperator::IsSome
+                | ConditionOperator::Exists
+                | ConditionOperator::NotExists
+        )
+    }
+
+    /// Whether this operator evaluates a condition group against each element
+    /// of a collection field.
+    pub fn is_quantifier(&self) -> bool {
+        matches!(self, ConditionOperator::AnyElement | ConditionOperator::AllElements)
+    }
+}
+
+/// How far apart two floats may be and still compare as equal.
+///
+/// Two values are equal if they differ by at most `absolute`, or by at most
+/// `relative` times the larger of their magnitudes. The default of zero for
+/// both means exact equality. Integers are always compared exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(any(feature = "serde", feature = "json_condition"), derive(serde::Serialize, serde::Deserialize))]
+pub struct Tolerance {
+    /// Maximum absolute difference
+    #[cfg_attr(any(feature = "serde", feature = "json_condition"), serde(default))]
+    pub absolute: f64,
+    /// Maximum difference relative to the larger magnitude
+    #[cfg_attr(any(feature = "serde", feature = "json_condition"), serde(default))]
+    pub relative: f64,
+}
+
+impl Tolerance {
+    /// Allow an absolute difference, e.g. `0.005` for "within half a cent".
+    pub fn absolute(absolute: f64) -> Self {
+        Self { absolute, relative: 0.0 }
+    }
+
+    /// Allow a difference relative to the values' magnitude, e.g. `0.01` for 1%.
+    pub fn relative(relative: f64) -> Self {
+        Self { absolute: 0.0, relative }
+    }
+
+    /// Whether two floats are equal within this tolerance.
+    pub fn equal(&self, a: f64, b: f64) -> bool {
+        if a == b {
+            return true;
+        }
+        let diff = (a - b).abs();
+        diff <= self.absolute || diff <= self.relative * a.abs().max(b.abs())
+    }
+}
+
+/// Unicode normalization form applied to strings before comparing.
+#[cfg(feature = "unicode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(any(feature = "serde", feature = "json_condition"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(feature = "serde", feature = "json_condition"), serde(rename_all = "lowercase"))]
+pub enum Normalization {
+    /// Canonical composition: `"e\u{301}"` and `"\u{e9}"` compare equal
+    Nfc,
+    /// Compatibility composition: also folds forms like `"\u{fb01}"` into `"fi"`
+    Nfkc,
+}
+
+#[cfg(feature = "unicode")]
+impl Normalization {
+    fn apply(self, text: &str) -> String {
+        use unicode_normalization::UnicodeNormalization;
+
+        match self {
+            Normalization::Nfc => text.nfc().collect(),
+            Normalization::Nfkc => text.nfkc().collect(),
+        }
+    }
+}
+
+/// How strings are prepared before they are compared.
+///
+/// Applies to both sides of every string operator, including `In`, `Between`
+/// and `Regex`. The default compares strings byte for byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(any(feature = "serde", feature = "json_condition"), derive(serde::Serialize, serde::Deserialize))]
+pub struct StringOptions {
+    /// Ignore case differences
+    #[cfg_attr(any(feature = "serde", feature = "json_condition"), serde(default))]
+    pub case_insensitive: bool,
+    /// Unicode normalization form to apply
+    #[cfg(feature = "unicode")]
+    #[cfg_attr(any(feature = "serde", feature = "json_condition"), serde(default))]
+    pub normalization: Option<Normalization>,
+    /// Ignore leading and trailing whitespace
+    #[cfg_attr(any(feature = "serde", feature = "json_condition"), serde(default))]
+    pub trim: bool,
+}
+
+impl StringOptions {
+    /// Ignore case differences.
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Apply a Unicode normalization form.
+    #[cfg(feature = "unicode")]
+    pub fn normalize(mut self, normalization: Normalization) -> Self {
+        self.normalization = Some(normalization);
+        self
+    }
+
+    /// Ignore leading and trailing whitespace.
+    pub fn trim(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    /// Prepare a string for comparison: trim, normalize, then lowercase.
+    pub fn apply<'s>(&self, text: &'s str) -> Cow<'s, str> {
+        let mut text = Cow::Borrowed(if self.trim { text.trim() } else { text });
+        #[cfg(feature = "unicode")]
+        if let Some(normalization) = self.normalization {
+            text = Cow::Owned(normalization.apply(&text));
+        }
+        if self.case_insensitive {
+            text = Cow::Owned(text.to_lowercase());
+        }
+        text
+    }
+
+    /// These options without case folding, for text matched by a regex that
+    /// handles case itself.
+    #[cfg(feature = "regex")]
+    pub(crate) fn case_sensitive(self) -> Self {
+        Self { case_insensitive: false, ..self }
+    }
 }
 
 // ============================================================================
-// Core condition types (always available, uses &dyn Any)
+// Core condition types (always available, uses dyn Any)
 // ============================================================================
 
 /// Selectors for targeting what to check in a condition
@@ -70,14 +255,26 @@ pub enum ConditionSelector<'a, T> {
     Type(String),
     /// Compare against a specific value
     Value(T),
-    /// Check a field value by name
-    FieldValue(&'a str, &'a dyn Any),
+    /// Check a field value by name; the operand is `Send + Sync` so that
+    /// conditions, and the matchers holding them, can move across threads
+    FieldValue(&'a str, &'a (dyn Any + Send + Sync)),
     /// Check a nested field path (e.g., ["address", "city"])
-    FieldPath(&'a [&'a str], &'a dyn Any),
+    FieldPath(&'a [&'a str], &'a (dyn Any + Send + Sync)),
+    /// Check a field value by name, owning both the name and the expected value
+    OwnedFieldValue(String, Box<dyn Any + Send + Sync>),
+    /// Check a nested field path, owning both the path and the expected value
+    OwnedFieldPath(Vec<String>, Box<dyn Any + Send + Sync>),
+    /// Compare a field with another field of the same value; either name may
+    /// be a dotted path (e.g. `("bid_price", "ask_price")`)
+    FieldToField(&'a str, &'a str),
     /// Negate a condition (inverts the result)
     Not(Box<Condition<'a, T>>),
     /// A nested group of conditions
     Nested(Box<NestedCondition<'a, T>>),
+    /// Evaluate a condition with floats compared within a tolerance
+    WithTolerance(Box<Condition<'a, T>>, Tolerance),
+    /// Evaluate a condition with strings prepared by the given options
+    WithStringOptions(Box<Condition<'a, T>>, StringOptions),
 }
 
 /// A single condition to evaluate
@@ -87,6 +284,177 @@ pub struct Condition<'a, T> {
     pub selector: ConditionSelector<'a, T>,
 }
 
+impl<T> Condition<'static, T> {
+    /// Create a field condition that owns its operands.
+    ///
+    /// Unlike [`ConditionSelector::FieldValue`], the resulting condition doesn't
+    /// borrow anything, so it can be built from runtime data and stored in
+    /// long-lived, thread-shared matchers.
+    pub fn owned_field(
+        field: impl Into<String>,
+        operator: ConditionOperator,
+        expected: impl Any + Send + Sync,
+    ) -> Self {
+        Condition {
+            selector: ConditionSelector::OwnedFieldValue(field.into(), Box::new(expected)),
+            operator,
+        }
+    }
+
+    /// Create a nested field path condition that owns its operands.
+    pub fn owned_path<S: Into<String>>(
+        path: impl IntoIterator<Item = S>,
+        operator: ConditionOperator,
+        expected: impl Any + Send + Sync,
+    ) -> Self {
+        Condition {
+            selector: ConditionSelector::OwnedFieldPath(
+                path.into_iter().map(Into::into).collect(),
+                Box::new(expected),
+            ),
+            operator,
+        }
+    }
+}
+
+impl<'a, T> Condition<'a, T> {
+    /// Compare floats within `tolerance` when this condition uses `Equals` or
+    /// `NotEquals`.
+    ///
+    /// ```rust
+    /// use condition_matcher::{field, Matcher, MatchableDerive, Matchable, RuleMatcher, Tolerance};
+    ///
+    /// #[derive(MatchableDerive, PartialEq)]
+    /// struct Quote {
+    ///     price: f64,
+    /// }
+    ///
+    /// let mut matcher = RuleMatcher::and();
+    /// matcher.add_condition(field::<Quote>("price").equals(&100.0).with_tolerance(Tolerance::absolute(0.005)));
+    /// assert!(matcher.matches(&Quote { price: 100.004 }));
+    /// ```
+    pub fn with_tolerance(self, tolerance: Tolerance) -> Self {
+        Condition {
+            operator: self.operator,
+            selector: ConditionSelector::WithTolerance(Box::new(self), tolerance),
+        }
+    }
+
+    /// Prepare strings with `options` before comparing them, e.g. to match
+    /// `"Bitcoin"` against `"bitcoin"`.
+    ///
+    /// ```rust
+    /// use condition_matcher::{field, Matcher, MatchableDerive, Matchable, RuleMatcher, StringOptions};
+    ///
+    /// #[derive(MatchableDerive, PartialEq)]
+    /// struct Asset {
+    ///     name: String,
+    /// }
+    ///
+    /// let mut matcher = RuleMatcher::and();
+    /// matcher.add_condition(
+    ///     field::<Asset>("name")
+    ///         .equals(&"bitcoin")
+    ///         .with_string_options(StringOptions::default().case_insensitive().trim()),
+    /// );
+    /// assert!(matcher.matches(&Asset { name: " Bitcoin ".to_string() }));
+    /// ```
+    ///
+    /// Call this before [`compile`](Self::compile), so `Regex` patterns are
+    /// compiled with the right case sensitivity.
+    pub fn with_string_options(self, options: StringOptions) -> Self {
+        Condition {
+            operator: self.operator,
+            selector: ConditionSelector::WithStringOptions(Box::new(self), options),
+        }
+    }
+
+    /// Pre-compile the operands of this condition, recursing into `Not`,
+    /// `Nested`, `WithTolerance` and `WithStringOptions`.
+    ///
+    /// `Regex` conditions whose expected value is a `&str` or `String` are
+    /// rewritten to own a compiled [`regex::Regex`], so the pattern is parsed
+    /// once instead of on every evaluation. An invalid pattern is reported as
+    /// [`MatchError::RegexError`].
+    pub fn compile(self) -> Result<Self, MatchError> {
+        self.compile_with(StringOptions::default())
+    }
+
+    #[cfg_attr(not(feature = "regex"), allow(clippy::only_used_in_recursion))]
+    fn compile_with(self, strings: StringOptions) -> Result<Self, MatchError> {
+        let Condition { operator, selector } = self;
+        let selector = match selector {
+            ConditionSelector::Not(inner) => {
+                ConditionSelector::Not(Box::new(inner.compile_with(strings)?))
+            }
+            ConditionSelector::WithTolerance(inner, tolerance) => {
+                ConditionSelector::WithTolerance(Box::new(inner.compile_with(strings)?), tolerance)
+            }
+            ConditionSelector::WithStringOptions(inner, options) => {
+                ConditionSelector::WithStringOptions(Box::new(inner.compile_with(options)?), options)
+            }
+            ConditionSelector::Nested(group) => {
+                ConditionSelector::Nested(Box::new(group.compile_with(strings)?))
+            }
+            #[cfg(feature = "regex")]
+            selector if operator == ConditionOperator::Regex => {
+                compile_regex_selector(selector, strings.case_insensitive)?
+            }
+            selector => selector,
+        };
+        Ok(Condition { operator, selector })
+    }
+}
+
+#[cfg(feature = "regex")]
+fn compile_regex_selector<'a, T>(
+    selector: ConditionSelector<'a, T>,
+    case_insensitive: bool,
+) -> Result<ConditionSelector<'a, T>, MatchError> {
+    use crate::evaluators::compile_regex;
+
+    let compile_regex = |pattern: &str| compile_regex(pattern, case_insensitive);
+
+    fn pattern(expected: &dyn Any) -> Option<&str> {
+        expected
+            .downcast_ref::<String>()
+            .map(|s| s.as_str())
+            .or_else(|| expected.downcast_ref::<&str>().copied())
+    }
+
+    Ok(match selector {
+        ConditionSelector::FieldValue(field, expected) => match pattern(expected) {
+            Some(p) => ConditionSelector::OwnedFieldValue(
+                field.to_string(),
+                Box::new(compile_regex(p)?),
+            ),
+            None => ConditionSelector::FieldValue(field, expected),
+        },
+        ConditionSelector::FieldPath(path, expected) => match pattern(expected) {
+            Some(p) => ConditionSelector::OwnedFieldPath(
+                path.iter().map(|s| s.to_string()).collect(),
+                Box::new(compile_regex(p)?),
+            ),
+            None => ConditionSelector::FieldPath(path, expected),
+        },
+        ConditionSelector::OwnedFieldValue(field, expected) => {
+            let expected = match pattern(expected.as_ref()) {
+                Some(p) => Box::new(compile_regex(p)?),
+                None => expected,
+            };
+            ConditionSelector::OwnedFieldValue(field, expected)
+        }
+        ConditionSelector::OwnedFieldPath(path, expected) => {
+            let expected = match pattern(expected.as_ref()) {
+                Some(p) => Box::new(compile_regex(p)?),
+                None => expected,
+            };
+            ConditionSelector::OwnedFieldPath(path, expected)
+        }
+        other => other,
+    })
+}
+
 /// A group of conditions combined with a logic mode
 #[derive(Debug)]
 pub struct NestedCondition<'a, T> {
@@ -98,12 +466,107 @@ pub struct NestedCondition<'a, T> {
     pub nested: Vec<Box<NestedCondition<'a, T>>>,
 }
 
+impl<'a, T> NestedCondition<'a, T> {
+    /// Pre-compile the operands of every condition in this group.
+    ///
+    /// See [`Condition::compile`].
+    pub fn compile(self) -> Result<Self, MatchError> {
+        self.compile_with(StringOptions::default())
+    }
+
+    fn compile_with(self, strings: StringOptions) -> Result<Self, MatchError> {
+        Ok(NestedCondition {
+            mode: self.mode,
+            rules: self
+                .rules
+                .into_iter()
+                .map(|c| c.compile_with(strings))
+                .collect::<Result<_, _>>()?,
+            nested: self
+                .nested
+                .into_iter()
+                .map(|n| n.compile_with(strings).map(Box::new))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
 // ============================================================================
 // JSON-serializable condition types (only with json_condition feature)
 // These are separate types that can be deserialized from JSON and converted
 // to the core types for evaluation.
 // ============================================================================
 
+/// The left-hand side of a JSON rule: a field, or a computed [`Expression`].
+///
+/// Deserializes from a field name or dotted path like `"user.age"`, or from an
+/// expression object like `{"$div": [{"$field": "change"}, {"$field": "price"}]}`.
+#[cfg(feature = "json_condition")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum JsonField {
+    /// A field name or dotted path
+    Path(String),
+    /// An arithmetic expression over fields
+    Expression(Expression),
+}
+
+#[cfg(feature = "json_condition")]
+impl JsonField {
+    /// The field path, unless this is an expression.
+    pub fn as_path(&self) -> Option<&str> {
+        match self {
+            JsonField::Path(path) => Some(path),
+            JsonField::Expression(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl std::fmt::Display for JsonField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonField::Path(path) => f.write_str(path),
+            JsonField::Expression(expression) => write!(f, "{}", expression),
+        }
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl From<String> for JsonField {
+    fn from(path: String) -> Self {
+        JsonField::Path(path)
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl From<&str> for JsonField {
+    fn from(path: &str) -> Self {
+        JsonField::Path(path.to_string())
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl From<Expression> for JsonField {
+    fn from(expression: Expression) -> Self {
+        JsonField::Expression(expression)
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl PartialEq<str> for JsonField {
+    fn eq(&self, other: &str) -> bool {
+        self.as_path() == Some(other)
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl PartialEq<&str> for JsonField {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_path() == Some(*other)
+    }
+}
+
 /// A JSON-serializable condition for field comparisons.
 /// 
 /// Deserializes from JSON like:
@@ -113,12 +576,75 @@ pub struct NestedCondition<'a, T> {
 #[cfg(feature = "json_condition")]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct JsonCondition {
-    /// The field to check (supports dotted paths like "user.age")
-    pub field: String,
+    /// The field to check (supports dotted paths like "user.age"), or an
+    /// arithmetic [`Expression`] over fields
+    pub field: JsonField,
     /// The comparison operator
     pub operator: ConditionOperator,
-    /// The value to compare against
+    /// The value to compare against, `{"$field": "name"}` to compare against
+    /// another field of the same value, or an arithmetic [`Expression`];
+    /// may be omitted for unary operators such as `exists`
+    #[serde(default)]
     pub value: serde_json::Value,
+    /// Maximum absolute difference for float `equals`/`not_equals`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+    /// Maximum difference relative to the larger magnitude for float
+    /// `equals`/`not_equals`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relative_tolerance: Option<f64>,
+    /// Ignore case differences in string operators
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub case_insensitive: bool,
+    /// Unicode normalization form applied to strings, `"nfc"` or `"nfkc"`
+    #[cfg(feature = "unicode")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<Normalization>,
+    /// Ignore leading and trailing whitespace in string operators
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub trim: bool,
+}
+
+#[cfg(feature = "json_condition")]
+impl JsonCondition {
+    /// The float tolerance set on this rule, if any.
+    pub fn float_tolerance(&self) -> Option<Tolerance> {
+        if self.tolerance.is_none() && self.relative_tolerance.is_none() {
+            return None;
+        }
+        Some(Tolerance {
+            absolute: self.tolerance.unwrap_or(0.0),
+            relative: self.relative_tolerance.unwrap_or(0.0),
+        })
+    }
+
+    /// How strings are prepared before comparing in this rule.
+    pub fn string_options(&self) -> StringOptions {
+        StringOptions {
+            case_insensitive: self.case_insensitive,
+            #[cfg(feature = "unicode")]
+            normalization: self.normalize,
+            trim: self.trim,
+        }
+    }
+
+    /// The field named by a `{"$field": "name"}` operand, if this rule
+    /// compares against another field of the same value.
+    pub fn field_reference(&self) -> Option<&str> {
+        let object = self.value.as_object()?;
+        if object.len() != 1 {
+            return None;
+        }
+        object.get("$field")?.as_str()
+    }
+
+    /// The operand as an arithmetic expression, if it is one.
+    ///
+    /// An operand that names an expression but isn't a valid one, such as
+    /// `{"$add": []}`, yields [`MatchError::InvalidOperand`].
+    pub fn value_expression(&self) -> Option<Result<Expression, MatchError>> {
+        Expression::from_operand(&self.value)
+    }
 }
 
 /// A JSON-serializable group of conditions with nested support.
@@ -147,6 +673,9 @@ pub struct JsonNestedCondition {
     /// Child groups (recursive)
     #[serde(default, alias = "nested_rules", alias = "nested_conditions")]
     pub nested: Vec<Box<JsonNestedCondition>>,
+    /// Invert the result of this group
+    #[serde(default, rename = "not", skip_serializing_if = "std::ops::Not::not")]
+    pub negate: bool,
 }
 
 // ============================================================================
@@ -159,6 +688,13 @@ impl<'a, T: Matchable + 'static> Predicate<T> for Condition<'a, T> {
     }
 
     fn test_detailed(&self, value: &T) -> ConditionResult {
+        self.test_with(value, CompareOptions::default())
+    }
+}
+
+impl<'a, T: Matchable + 'static> Condition<'a, T> {
+    /// Evaluate with the tolerance and string options set by enclosing wrappers.
+    fn test_with(&self, value: &T, options: CompareOptions) -> ConditionResult {
         match &self.selector {
             ConditionSelector::Length(expected) => {
                 LengthEvaluator::evaluate(value, *expected, &self.operator)
@@ -170,37 +706,62 @@ impl<'a, T: Matchable + 'static> Predicate<T> for Condition<'a, T> {
                 ValueEvaluator::evaluate(value, expected, &self.operator)
             }
             ConditionSelector::FieldValue(field, expected) => {
-                FieldEvaluator::evaluate(value, field, *expected, &self.operator)
+                FieldEvaluator::evaluate(value, field, *expected, &self.operator, options)
             }
             ConditionSelector::FieldPath(path, expected) => {
-                PathEvaluator::evaluate(value, path, *expected, &self.operator)
+                PathEvaluator::evaluate(value, path, *expected, &self.operator, options)
+            }
+            ConditionSelector::OwnedFieldValue(field, expected) => {
+                FieldEvaluator::evaluate(value, field, expected.as_ref(), &self.operator, options)
+            }
+            ConditionSelector::OwnedFieldPath(path, expected) => {
+                let path: Vec<&str> = path.iter().map(String::as_str).collect();
+                PathEvaluator::evaluate(value, &path, expected.as_ref(), &self.operator, options)
+            }
+            ConditionSelector::FieldToField(field, other) => {
+                FieldEvaluator::evaluate_reference(value, field, other, &self.operator, options)
             }
             ConditionSelector::Not(inner) => {
-                let mut result = inner.test_detailed(value);
+                let mut result = inner.test_with(value, options);
                 result.passed = !result.passed;
                 result.description = format!("NOT({})", result.description);
                 result
             }
-            ConditionSelector::Nested(group) => evaluate_nested(value, group),
+            ConditionSelector::Nested(group) => evaluate_nested_with(value, group, options),
+            ConditionSelector::WithTolerance(inner, tolerance) => {
+                inner.test_with(value, CompareOptions { tolerance: *tolerance, ..options })
+            }
+            ConditionSelector::WithStringOptions(inner, strings) => {
+                inner.test_with(value, CompareOptions { strings: *strings, ..options })
+            }
         }
     }
 }
 
 /// Evaluate a nested condition group.
-fn evaluate_nested<'a, T: Matchable + 'static>(
+pub(crate) fn evaluate_nested<'a, T: Matchable + 'static>(
+    value: &T,
+    group: &NestedCondition<'a, T>,
+) -> ConditionResult {
+    evaluate_nested_with(value, group, CompareOptions::default())
+}
+
+/// Evaluate a nested condition group under the options of enclosing wrappers.
+fn evaluate_nested_with<'a, T: Matchable + 'static>(
     value: &T,
     group: &NestedCondition<'a, T>,
+    options: CompareOptions,
 ) -> ConditionResult {
     let mut results = Vec::new();
 
     // Evaluate all rules at this level
     for condition in &group.rules {
-        results.push(condition.test_detailed(value));
+        results.push(condition.test_with(value, options));
     }
 
     // Evaluate nested groups recursively
     for nested_group in &group.nested {
-        results.push(evaluate_nested(value, nested_group));
+        results.push(evaluate_nested_with(value, nested_group, options));
     }
 
     let passed = match group.mode {
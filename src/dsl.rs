@@ -0,0 +1,1221 @@
+//! Text expression language for JSON conditions.
+//!
+//! Conditions can be written as compact expressions instead of nested JSON:
+//!
+//! ```text
+//! price >= 100 && (symbol starts_with "BTC" || volume > 1e6)
+//! ```
+//!
+//! The expression is compiled into a [`JsonNestedCondition`], so it can be
+//! stored, serialized and evaluated exactly like a JSON-authored condition.
+//! [`JsonNestedCondition`] implements [`Display`](std::fmt::Display), which
+//! prints a condition back into this syntax.
+//!
+//! ## Grammar
+//!
+//! - Comparisons: `field <op> literal`, where `field` is a dotted path
+//!   (`order.items.0.qty`), a backtick-quoted name (`` `unit-price` ``) or an
+//!   arithmetic [`Expression`] over fields.
+//! - Expressions: `+`, `-`, `*` and `/` with the usual precedence, number
+//!   literals, parentheses and the functions `abs(x)`, `min(x, ...)` and
+//!   `max(x, ...)`, e.g. `abs(price - open) / open > 0.05`.
+//! - Symbolic operators: `==`, `!=`, `>`, `>=`, `<`, `<=`.
+//! - Word operators: any operator by its snake_case name, e.g. `contains`,
+//!   `starts_with`, `regex`. Unary operators such as `is_none` take no literal.
+//! - Literals: JSON strings, numbers, `true`, `false`, `null`, arrays
+//!   (`status in ["open", "pending"]`) and objects
+//!   (`price between {min: 10, max: 20, inclusive: [true, false]}`).
+//! - Rule options: a trailing `with {...}` object sets the float tolerance
+//!   and string options of a comparison, using the keys of the JSON form:
+//!   `price == 100 with {tolerance: 0.005}`,
+//!   `name == "btc" with {case_insensitive: true, trim: true}`. The keys are
+//!   `tolerance`, `relative_tolerance`, `case_insensitive`, `trim` and, with
+//!   the `unicode` feature, `normalize` (`"nfc"` or `"nfkc"`).
+//! - Logic, from lowest to highest precedence: `||` (OR), `^` (XOR, exactly
+//!   one operand matches), `&&` (AND), `!` (NOT). Parentheses group.
+//!
+//! Expressions can be used as operands via object literals
+//! (`volume > {"$mul": [{"$field": "avg_volume"}, 3]}`).
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{Chars, FromStr};
+
+#[cfg(feature = "unicode")]
+use crate::condition::Normalization;
+use crate::{
+    condition::{ConditionMode, ConditionOperator, JsonCondition, JsonField, JsonNestedCondition},
+    error::ParseError,
+    expression::Expression,
+};
+
+/// Parse an expression into a [`JsonNestedCondition`].
+///
+/// # Example
+///
+/// ```rust
+/// use condition_matcher::{parse_condition, ConditionMode};
+///
+/// let condition = parse_condition(r#"price >= 100 && symbol starts_with "BTC""#).unwrap();
+/// assert_eq!(condition.mode, ConditionMode::AND);
+/// assert_eq!(condition.rules.len(), 2);
+/// ```
+pub fn parse_condition(input: &str) -> Result<JsonNestedCondition, ParseError> {
+    let tokens = Lexer::new(input).tokenize()?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_or()?;
+    parser.expect_end()?;
+    Ok(expr.into_group())
+}
+
+impl FromStr for JsonNestedCondition {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_condition(s)
+    }
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Str(String),
+    Number(serde_json::Number),
+    Dot,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Colon,
+    Comma,
+    And,
+    Or,
+    Xor,
+    Not,
+    Eq,
+    NotEq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    End,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Ident(name) => write!(f, "'{}'", name),
+            TokenKind::Str(s) => write!(f, "string {:?}", s),
+            TokenKind::Number(n) => write!(f, "number {}", n),
+            TokenKind::Dot => write!(f, "'.'"),
+            TokenKind::LParen => write!(f, "'('"),
+            TokenKind::RParen => write!(f, "')'"),
+            TokenKind::LBracket => write!(f, "'['"),
+            TokenKind::LBrace => write!(f, "'{{'"),
+            TokenKind::RBrace => write!(f, "'}}'"),
+            TokenKind::Colon => write!(f, "':'"),
+            TokenKind::RBracket => write!(f, "']'"),
+            TokenKind::Comma => write!(f, "','"),
+            TokenKind::And => write!(f, "'&&'"),
+            TokenKind::Or => write!(f, "'||'"),
+            TokenKind::Xor => write!(f, "'^'"),
+            TokenKind::Not => write!(f, "'!'"),
+            TokenKind::Eq => write!(f, "'=='"),
+            TokenKind::NotEq => write!(f, "'!='"),
+            TokenKind::Gt => write!(f, "'>'"),
+            TokenKind::Gte => write!(f, "'>='"),
+            TokenKind::Lt => write!(f, "'<'"),
+            TokenKind::Lte => write!(f, "'<='"),
+            TokenKind::Plus => write!(f, "'+'"),
+            TokenKind::Minus => write!(f, "'-'"),
+            TokenKind::Star => write!(f, "'*'"),
+            TokenKind::Slash => write!(f, "'/'"),
+            TokenKind::End => write!(f, "end of input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    line: usize,
+    column: usize,
+}
+
+impl Token {
+    /// Whether this token can end an operand, as opposed to a word operator
+    /// like `contains` or a symbol like `==` that expects one to follow.
+    fn ends_operand(&self) -> bool {
+        match &self.kind {
+            TokenKind::Number(_)
+            | TokenKind::Str(_)
+            | TokenKind::RParen
+            | TokenKind::RBracket
+            | TokenKind::RBrace => true,
+            TokenKind::Ident(name) => {
+                serde_json::from_value::<ConditionOperator>(serde_json::Value::String(name.clone()))
+                    .is_err()
+            }
+            _ => false,
+        }
+    }
+}
+
+struct Lexer<'s> {
+    chars: Peekable<Chars<'s>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'s> Lexer<'s> {
+    fn new(input: &'s str) -> Self {
+        Self {
+            chars: input.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, message: impl Into<String>, line: usize, column: usize) -> ParseError {
+        ParseError {
+            message: message.into(),
+            line,
+            column,
+        }
+    }
+
+    fn tokenize(mut self) -> Result<Vec<Token>, ParseError> {
+        let mut tokens: Vec<Token> = Vec::new();
+
+        loop {
+            while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+                self.bump();
+            }
+
+            let (line, column) = (self.line, self.column);
+            let Some(&c) = self.chars.peek() else {
+                tokens.push(Token {
+                    kind: TokenKind::End,
+                    line,
+                    column,
+                });
+                return Ok(tokens);
+            };
+
+            // Path segments after a dot may be array indices, which must not be
+            // lexed as numbers (`items.0.1` is a path, not `items` `.` `0.1`).
+            let after_dot = matches!(
+                tokens.last(),
+                Some(Token {
+                    kind: TokenKind::Dot,
+                    ..
+                })
+            );
+
+            // A '-' after an operand subtracts (`price -1`); elsewhere it
+            // starts a negative number (`price > -1`, `[1, -2]`).
+            let after_operand = tokens.last().is_some_and(Token::ends_operand);
+
+            let kind = match c {
+                '(' => self.single(TokenKind::LParen),
+                ')' => self.single(TokenKind::RParen),
+                '[' => self.single(TokenKind::LBracket),
+                '{' => self.single(TokenKind::LBrace),
+                '}' => self.single(TokenKind::RBrace),
+                ':' => self.single(TokenKind::Colon),
+                ']' => self.single(TokenKind::RBracket),
+                ',' => self.single(TokenKind::Comma),
+                '.' => self.single(TokenKind::Dot),
+                '^' => self.single(TokenKind::Xor),
+                '+' => self.single(TokenKind::Plus),
+                '*' => self.single(TokenKind::Star),
+                '/' => self.single(TokenKind::Slash),
+                '-' if after_operand
+                    || !self.chars.clone().nth(1).is_some_and(|c| c.is_ascii_digit()) =>
+                {
+                    self.single(TokenKind::Minus)
+                }
+                '&' => self.pair('&', TokenKind::And, line, column)?,
+                '|' => self.pair('|', TokenKind::Or, line, column)?,
+                '=' => self.pair('=', TokenKind::Eq, line, column)?,
+                '!' => self.with_eq(TokenKind::Not, TokenKind::NotEq),
+                '>' => self.with_eq(TokenKind::Gt, TokenKind::Gte),
+                '<' => self.with_eq(TokenKind::Lt, TokenKind::Lte),
+                '"' => TokenKind::Str(self.string(line, column)?),
+                '`' => TokenKind::Ident(self.quoted_ident(line, column)?),
+                c if after_dot && c.is_ascii_digit() => {
+                    TokenKind::Ident(self.take_while(|c| c.is_ascii_digit()))
+                }
+                c if c.is_ascii_digit() || c == '-' => {
+                    TokenKind::Number(self.number(line, column)?)
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    TokenKind::Ident(self.take_while(|c| c.is_alphanumeric() || c == '_'))
+                }
+                other => {
+                    return Err(self.error(
+                        format!("unexpected character '{}'", other),
+                        line,
+                        column,
+                    ));
+                }
+            };
+
+            tokens.push(Token { kind, line, column });
+        }
+    }
+
+    fn single(&mut self, kind: TokenKind) -> TokenKind {
+        self.bump();
+        kind
+    }
+
+    fn pair(
+        &mut self,
+        second: char,
+        kind: TokenKind,
+        line: usize,
+        column: usize,
+    ) -> Result<TokenKind, ParseError> {
+        let first = self.bump().unwrap_or_default();
+        if self.chars.peek() == Some(&second) {
+            self.bump();
+            Ok(kind)
+        } else {
+            Err(self.error(
+                format!("expected '{}{}', found '{}'", first, second, first),
+                line,
+                column,
+            ))
+        }
+    }
+
+    fn with_eq(&mut self, plain: TokenKind, with_eq: TokenKind) -> TokenKind {
+        self.bump();
+        if self.chars.peek() == Some(&'=') {
+            self.bump();
+            with_eq
+        } else {
+            plain
+        }
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> String {
+        let mut out = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if !pred(c) {
+                break;
+            }
+            out.push(c);
+            self.bump();
+        }
+        out
+    }
+
+    fn string(&mut self, line: usize, column: usize) -> Result<String, ParseError> {
+        // Collect the raw literal (quotes and escapes included) and let serde_json
+        // decode it, so the accepted syntax is exactly that of JSON strings.
+        let mut raw = String::new();
+        raw.push(self.bump().unwrap_or('"'));
+        loop {
+            match self.bump() {
+                Some('"') => {
+                    raw.push('"');
+                    break;
+                }
+                Some('\\') => {
+                    raw.push('\\');
+                    match self.bump() {
+                        Some(c) => raw.push(c),
+                        None => break,
+                    }
+                }
+                Some('\n') | None => {
+                    return Err(self.error("unterminated string literal", line, column));
+                }
+                Some(c) => raw.push(c),
+            }
+        }
+        serde_json::from_str::<String>(&raw)
+            .map_err(|e| self.error(format!("invalid string literal: {}", e), line, column))
+    }
+
+    fn quoted_ident(&mut self, line: usize, column: usize) -> Result<String, ParseError> {
+        self.bump();
+        let name = self.take_while(|c| c != '`' && c != '\n');
+        if self.bump() != Some('`') {
+            return Err(self.error("unterminated quoted field name", line, column));
+        }
+        Ok(name)
+    }
+
+    fn number(&mut self, line: usize, column: usize) -> Result<serde_json::Number, ParseError> {
+        let mut raw = String::new();
+        if self.chars.peek() == Some(&'-') {
+            raw.push('-');
+            self.bump();
+        }
+        raw.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        if self.chars.peek() == Some(&'.') {
+            raw.push('.');
+            self.bump();
+            raw.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+        if matches!(self.chars.peek(), Some('e') | Some('E')) {
+            raw.push('e');
+            self.bump();
+            if let Some(&sign) = self.chars.peek()
+                && (sign == '+' || sign == '-')
+            {
+                raw.push(sign);
+                self.bump();
+            }
+            raw.push_str(&self.take_while(|c| c.is_ascii_digit()));
+        }
+
+        let invalid = || self.error(format!("invalid number '{}'", raw), line, column);
+        if let Ok(v) = raw.parse::<i64>() {
+            return Ok(v.into());
+        }
+        if let Ok(v) = raw.parse::<u64>() {
+            return Ok(v.into());
+        }
+        raw.parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .ok_or_else(invalid)
+    }
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// Intermediate expression tree, flattened into a [`JsonNestedCondition`].
+enum Expr {
+    Rule(JsonCondition),
+    Group {
+        mode: ConditionMode,
+        items: Vec<Expr>,
+        negate: bool,
+    },
+}
+
+impl Expr {
+    fn negated(self) -> Expr {
+        match self {
+            Expr::Rule(rule) => Expr::Group {
+                mode: ConditionMode::AND,
+                items: vec![Expr::Rule(rule)],
+                negate: true,
+            },
+            Expr::Group {
+                mode,
+                items,
+                negate,
+            } => Expr::Group {
+                mode,
+                items,
+                negate: !negate,
+            },
+        }
+    }
+
+    fn into_group(self) -> JsonNestedCondition {
+        match self {
+            Expr::Rule(rule) => JsonNestedCondition {
+                mode: ConditionMode::AND,
+                rules: vec![rule],
+                nested: Vec::new(),
+                negate: false,
+            },
+            Expr::Group {
+                mode,
+                items,
+                negate,
+            } => {
+                let mut group = JsonNestedCondition {
+                    mode,
+                    rules: Vec::new(),
+                    nested: Vec::new(),
+                    negate,
+                };
+                for item in items {
+                    match item {
+                        Expr::Rule(rule) => group.rules.push(rule),
+                        nested => group.nested.push(Box::new(nested.into_group())),
+                    }
+                }
+                group
+            }
+        }
+    }
+}
+
+/// Options accepted in a rule's `with {...}` clause.
+#[derive(Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleOptions {
+    #[serde(default)]
+    tolerance: Option<f64>,
+    #[serde(default)]
+    relative_tolerance: Option<f64>,
+    #[serde(default)]
+    case_insensitive: bool,
+    #[cfg(feature = "unicode")]
+    #[serde(default)]
+    normalize: Option<Normalization>,
+    #[serde(default)]
+    trim: bool,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos.min(self.tokens.len() - 1)]
+    }
+
+    fn peek_next(&self) -> &TokenKind {
+        &self.tokens[(self.pos + 1).min(self.tokens.len() - 1)].kind
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.peek().clone();
+        if self.pos < self.tokens.len() - 1 {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error_at(token: &Token, message: impl Into<String>) -> ParseError {
+        ParseError {
+            message: message.into(),
+            line: token.line,
+            column: token.column,
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), ParseError> {
+        let token = self.peek();
+        match token.kind {
+            TokenKind::End => Ok(()),
+            _ => Err(Self::error_at(token, format!("unexpected {}", token.kind))),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ParseError> {
+        self.parse_chain(TokenKind::Or, ConditionMode::OR, Self::parse_xor)
+    }
+
+    fn parse_xor(&mut self) -> Result<Expr, ParseError> {
+        self.parse_chain(TokenKind::Xor, ConditionMode::XOR, Self::parse_and)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ParseError> {
+        self.parse_chain(TokenKind::And, ConditionMode::AND, Self::parse_unary)
+    }
+
+    /// Parse `operand (separator operand)*` into a single group.
+    fn parse_chain(
+        &mut self,
+        separator: TokenKind,
+        mode: ConditionMode,
+        operand: fn(&mut Self) -> Result<Expr, ParseError>,
+    ) -> Result<Expr, ParseError> {
+        let first = operand(self)?;
+        if self.peek().kind != separator {
+            return Ok(first);
+        }
+
+        let mut items = Vec::new();
+        let mut push = |expr: Expr| match expr {
+            // AND and OR are associative, so un-negated groups of the same
+            // mode are merged. XOR ("exactly one") is not, so it's kept nested.
+            Expr::Group {
+                mode: inner,
+                items: inner_items,
+                negate: false,
+            } if inner == mode && mode != ConditionMode::XOR => items.extend(inner_items),
+            other => items.push(other),
+        };
+        push(first);
+        while self.peek().kind == separator {
+            self.advance();
+            push(operand(self)?);
+        }
+
+        Ok(Expr::Group {
+            mode,
+            items,
+            negate: false,
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ParseError> {
+        if self.peek().kind == TokenKind::Not {
+            self.advance();
+            return Ok(self.parse_unary()?.negated());
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ParseError> {
+        if self.peek().kind != TokenKind::LParen {
+            return self.parse_comparison().map(Expr::Rule);
+        }
+
+        // A parenthesis opens either a group of conditions or an arithmetic
+        // field expression such as `(bid + ask) / 2 > 100`. Try the group
+        // first and report the error of whichever attempt got further.
+        let start = self.pos;
+        let group_err = match self.parse_group() {
+            Ok(expr) => return Ok(expr),
+            Err(err) => err,
+        };
+        let group_end = self.pos;
+        self.pos = start;
+        match self.parse_comparison() {
+            Ok(rule) => Ok(Expr::Rule(rule)),
+            Err(err) if self.pos > group_end => Err(err),
+            Err(_) => Err(group_err),
+        }
+    }
+
+    fn parse_group(&mut self) -> Result<Expr, ParseError> {
+        let open = self.advance();
+        if self.peek().kind == TokenKind::RParen {
+            self.advance();
+            return Ok(Expr::Group {
+                mode: ConditionMode::AND,
+                items: Vec::new(),
+                negate: false,
+            });
+        }
+
+        let expr = self.parse_or()?;
+        let close = self.advance();
+        match close.kind {
+            TokenKind::RParen => Ok(expr),
+            TokenKind::End => Err(Self::error_at(
+                &open,
+                "unclosed '(': expected ')' before end of input",
+            )),
+            ref other => Err(Self::error_at(
+                &close,
+                format!("expected ')', found {}", other),
+            )),
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<JsonCondition, ParseError> {
+        let field = match self.parse_sum()? {
+            Expression::Field(path) => JsonField::Path(path),
+            expression => JsonField::Expression(expression),
+        };
+
+        let op_token = self.advance();
+        let operator = match &op_token.kind {
+            TokenKind::Eq => ConditionOperator::Equals,
+            TokenKind::NotEq => ConditionOperator::NotEquals,
+            TokenKind::Gt => ConditionOperator::GreaterThan,
+            TokenKind::Gte => ConditionOperator::GreaterThanOrEqual,
+            TokenKind::Lt => ConditionOperator::LessThan,
+            TokenKind::Lte => ConditionOperator::LessThanOrEqual,
+            TokenKind::Ident(name) => {
+                serde_json::from_value(serde_json::Value::String(name.clone())).map_err(|_| {
+                    Self::error_at(&op_token, format!("unknown operator '{}'", name))
+                })?
+            }
+            other => {
+                return Err(Self::error_at(
+                    &op_token,
+                    format!("expected an operator after '{}', found {}", field, other),
+                ));
+            }
+        };
+
+        let value = if operator.is_unary() && !self.at_literal() {
+            serde_json::Value::Null
+        } else {
+            self.parse_literal()?
+        };
+        let options = self.parse_options()?;
+
+        Ok(JsonCondition {
+            field,
+            operator,
+            value,
+            tolerance: options.tolerance,
+            relative_tolerance: options.relative_tolerance,
+            case_insensitive: options.case_insensitive,
+            #[cfg(feature = "unicode")]
+            normalize: options.normalize,
+            trim: options.trim,
+        })
+    }
+
+    /// Parse an optional `with {...}` clause following a comparison.
+    fn parse_options(&mut self) -> Result<RuleOptions, ParseError> {
+        if !matches!(&self.peek().kind, TokenKind::Ident(name) if name == "with") {
+            return Ok(RuleOptions::default());
+        }
+        self.advance();
+
+        let open = self.advance();
+        if open.kind != TokenKind::LBrace {
+            return Err(Self::error_at(
+                &open,
+                format!("expected '{{' after 'with', found {}", open.kind),
+            ));
+        }
+        let object = self.parse_object(&open)?;
+        serde_json::from_value(object)
+            .map_err(|e| Self::error_at(&open, format!("invalid rule options: {}", e)))
+    }
+
+    /// Parse `term (('+' | '-') term)*`. An unparenthesized chain of `+`
+    /// becomes a single `$add`.
+    fn parse_sum(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.parse_term()?;
+        let mut chained = false;
+        loop {
+            match &self.peek().kind {
+                TokenKind::Plus => {
+                    self.advance();
+                    let rhs = self.parse_term()?;
+                    expr = match expr {
+                        Expression::Add(mut operands) if chained => {
+                            operands.push(rhs);
+                            Expression::Add(operands)
+                        }
+                        lhs => Expression::Add(vec![lhs, rhs]),
+                    };
+                    chained = true;
+                }
+                TokenKind::Minus => {
+                    self.advance();
+                    expr = Expression::Sub(Box::new(expr), Box::new(self.parse_term()?));
+                    chained = false;
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    /// Parse `factor (('*' | '/') factor)*`. An unparenthesized chain of `*`
+    /// becomes a single `$mul`.
+    fn parse_term(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.parse_factor()?;
+        let mut chained = false;
+        loop {
+            match self.peek().kind {
+                TokenKind::Star => {
+                    self.advance();
+                    let rhs = self.parse_factor()?;
+                    expr = match expr {
+                        Expression::Mul(mut operands) if chained => {
+                            operands.push(rhs);
+                            Expression::Mul(operands)
+                        }
+                        lhs => Expression::Mul(vec![lhs, rhs]),
+                    };
+                    chained = true;
+                }
+                TokenKind::Slash => {
+                    self.advance();
+                    expr = Expression::Div(Box::new(expr), Box::new(self.parse_factor()?));
+                    chained = false;
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+
+    /// Parse a field, a number, a function call or a parenthesized expression.
+    fn parse_factor(&mut self) -> Result<Expression, ParseError> {
+        let token = self.peek().clone();
+        match token.kind {
+            TokenKind::Number(n) => {
+                self.advance();
+                Ok(Expression::Literal(n.as_f64().unwrap_or_default()))
+            }
+            TokenKind::LParen => {
+                self.advance();
+                let expr = self.parse_sum()?;
+                let close = self.advance();
+                match close.kind {
+                    TokenKind::RParen => Ok(expr),
+                    TokenKind::End => Err(Self::error_at(
+                        &token,
+                        "unclosed '(': expected ')' before end of input",
+                    )),
+                    ref other => Err(Self::error_at(
+                        &close,
+                        format!("expected ')', found {}", other),
+                    )),
+                }
+            }
+            TokenKind::Ident(ref name) if self.peek_next() == &TokenKind::LParen => {
+                self.advance();
+                let open = self.advance();
+                let mut args = self.parse_arguments(&open)?;
+                match name.as_str() {
+                    "abs" if args.len() == 1 => Ok(Expression::Abs(Box::new(args.remove(0)))),
+                    "abs" => Err(Self::error_at(&token, "abs() takes exactly one argument")),
+                    "min" | "max" if args.is_empty() => Err(Self::error_at(
+                        &token,
+                        format!("{}() takes at least one argument", name),
+                    )),
+                    "min" => Ok(Expression::Min(args)),
+                    "max" => Ok(Expression::Max(args)),
+                    other => Err(Self::error_at(&token, format!("unknown function '{}'", other))),
+                }
+            }
+            _ => self.parse_field().map(Expression::Field),
+        }
+    }
+
+    fn parse_arguments(&mut self, open: &Token) -> Result<Vec<Expression>, ParseError> {
+        let mut args = Vec::new();
+        if self.peek().kind == TokenKind::RParen {
+            self.advance();
+            return Ok(args);
+        }
+        loop {
+            args.push(self.parse_sum()?);
+            let token = self.advance();
+            match token.kind {
+                TokenKind::Comma => continue,
+                TokenKind::RParen => return Ok(args),
+                TokenKind::End => {
+                    return Err(Self::error_at(
+                        open,
+                        "unclosed '(': expected ')' before end of input",
+                    ));
+                }
+                ref other => {
+                    return Err(Self::error_at(
+                        &token,
+                        format!("expected ',' or ')', found {}", other),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn parse_field(&mut self) -> Result<String, ParseError> {
+        let mut segments = Vec::new();
+        loop {
+            let token = self.advance();
+            match token.kind {
+                TokenKind::Ident(name) => segments.push(name),
+                ref other => {
+                    return Err(Self::error_at(
+                        &token,
+                        format!("expected a field name, found {}", other),
+                    ));
+                }
+            }
+            if self.peek().kind != TokenKind::Dot {
+                return Ok(segments.join("."));
+            }
+            self.advance();
+        }
+    }
+
+    fn at_literal(&self) -> bool {
+        match &self.peek().kind {
+            TokenKind::Str(_) | TokenKind::Number(_) | TokenKind::LBracket | TokenKind::LBrace => {
+                true
+            }
+            TokenKind::Ident(name) => matches!(name.as_str(), "true" | "false" | "null"),
+            _ => false,
+        }
+    }
+
+    fn parse_literal(&mut self) -> Result<serde_json::Value, ParseError> {
+        let token = self.advance();
+        match token.kind {
+            TokenKind::Str(s) => Ok(serde_json::Value::String(s)),
+            TokenKind::Number(n) => Ok(serde_json::Value::Number(n)),
+            TokenKind::Ident(name) if name == "true" => Ok(serde_json::Value::Bool(true)),
+            TokenKind::Ident(name) if name == "false" => Ok(serde_json::Value::Bool(false)),
+            TokenKind::Ident(name) if name == "null" => Ok(serde_json::Value::Null),
+            TokenKind::LBracket => self.parse_array(&token),
+            TokenKind::LBrace => self.parse_object(&token),
+            ref other => Err(Self::error_at(
+                &token,
+                format!("expected a value, found {}", other),
+            )),
+        }
+    }
+
+    fn parse_array(&mut self, open: &Token) -> Result<serde_json::Value, ParseError> {
+        let mut items = Vec::new();
+        if self.peek().kind == TokenKind::RBracket {
+            self.advance();
+            return Ok(serde_json::Value::Array(items));
+        }
+        loop {
+            items.push(self.parse_literal()?);
+            let token = self.advance();
+            match token.kind {
+                TokenKind::Comma => continue,
+                TokenKind::RBracket => return Ok(serde_json::Value::Array(items)),
+                TokenKind::End => {
+                    return Err(Self::error_at(
+                        open,
+                        "unclosed '[': expected ']' before end of input",
+                    ));
+                }
+                ref other => {
+                    return Err(Self::error_at(
+                        &token,
+                        format!("expected ',' or ']', found {}", other),
+                    ));
+                }
+            }
+        }
+    }
+
+    fn parse_object(&mut self, open: &Token) -> Result<serde_json::Value, ParseError> {
+        let mut map = serde_json::Map::new();
+        if self.peek().kind == TokenKind::RBrace {
+            self.advance();
+            return Ok(serde_json::Value::Object(map));
+        }
+        loop {
+            let key_token = self.advance();
+            let key = match key_token.kind {
+                TokenKind::Str(key) | TokenKind::Ident(key) => key,
+                TokenKind::End => {
+                    return Err(Self::error_at(
+                        open,
+                        "unclosed '{': expected '}' before end of input",
+                    ));
+                }
+                ref other => {
+                    return Err(Self::error_at(
+                        &key_token,
+                        format!("expected a key, found {}", other),
+                    ));
+                }
+            };
+            let colon = self.advance();
+            if colon.kind != TokenKind::Colon {
+                return Err(Self::error_at(
+                    &colon,
+                    format!("expected ':' after key, found {}", colon.kind),
+                ));
+            }
+            map.insert(key, self.parse_literal()?);
+
+            let token = self.advance();
+            match token.kind {
+                TokenKind::Comma => continue,
+                TokenKind::RBrace => return Ok(serde_json::Value::Object(map)),
+                TokenKind::End => {
+                    return Err(Self::error_at(
+                        open,
+                        "unclosed '{': expected '}' before end of input",
+                    ));
+                }
+                ref other => {
+                    return Err(Self::error_at(
+                        &token,
+                        format!("expected ',' or '}}', found {}", other),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Pretty-printing
+// ============================================================================
+
+fn operator_symbol(operator: &ConditionOperator) -> Option<&'static str> {
+    match operator {
+        ConditionOperator::Equals => Some("=="),
+        ConditionOperator::NotEquals => Some("!="),
+        ConditionOperator::GreaterThan => Some(">"),
+        ConditionOperator::GreaterThanOrEqual => Some(">="),
+        ConditionOperator::LessThan => Some("<"),
+        ConditionOperator::LessThanOrEqual => Some("<="),
+        _ => None,
+    }
+}
+
+pub(crate) fn is_plain_path(field: &str) -> bool {
+    field.split('.').enumerate().all(|(i, segment)| {
+        let mut chars = segment.chars();
+        match chars.next() {
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                chars.all(|c| c.is_alphanumeric() || c == '_')
+                    && !matches!(segment, "true" | "false" | "null")
+            }
+            Some(c) if c.is_ascii_digit() && i > 0 => chars.all(|c| c.is_ascii_digit()),
+            _ => false,
+        }
+    })
+}
+
+impl fmt::Display for JsonCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            JsonField::Path(path) if is_plain_path(path) => write!(f, "{}", path)?,
+            JsonField::Path(path) => write!(f, "`{}`", path)?,
+            JsonField::Expression(expression) => write!(f, "{}", expression)?,
+        }
+
+        match operator_symbol(&self.operator) {
+            Some(symbol) => write!(f, " {}", symbol)?,
+            None => {
+                let name = serde_json::to_value(self.operator).map_err(|_| fmt::Error)?;
+                write!(f, " {}", name.as_str().unwrap_or_default())?;
+            }
+        }
+
+        if !(self.operator.is_unary() && self.value.is_null()) {
+            write!(f, " {}", self.value)?;
+        }
+        self.write_options(f)
+    }
+}
+
+impl JsonCondition {
+    /// Write the `with {...}` clause for the options set on this rule.
+    fn write_options(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut options = Vec::new();
+        if let Some(tolerance) = self.tolerance {
+            options.push(format!("tolerance: {}", serde_json::Value::from(tolerance)));
+        }
+        if let Some(tolerance) = self.relative_tolerance {
+            options.push(format!("relative_tolerance: {}", serde_json::Value::from(tolerance)));
+        }
+        if self.case_insensitive {
+            options.push("case_insensitive: true".to_string());
+        }
+        #[cfg(feature = "unicode")]
+        if let Some(normalize) = self.normalize {
+            let name = serde_json::to_value(normalize).map_err(|_| fmt::Error)?;
+            options.push(format!("normalize: {}", name));
+        }
+        if self.trim {
+            options.push("trim: true".to_string());
+        }
+
+        if options.is_empty() {
+            return Ok(());
+        }
+        write!(f, " with {{{}}}", options.join(", "))
+    }
+}
+
+impl JsonNestedCondition {
+    fn item_count(&self) -> usize {
+        self.rules.len() + self.nested.len()
+    }
+
+    fn write_body(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = match self.mode {
+            ConditionMode::AND => " && ",
+            ConditionMode::OR => " || ",
+            ConditionMode::XOR => " ^ ",
+        };
+
+        let mut first = true;
+        for rule in &self.rules {
+            if !first {
+                f.write_str(separator)?;
+            }
+            first = false;
+            write!(f, "{}", rule)?;
+        }
+        for nested in &self.nested {
+            if !first {
+                f.write_str(separator)?;
+            }
+            first = false;
+            nested.write_operand(f)?;
+        }
+        Ok(())
+    }
+
+    /// Write this group as an operand of an enclosing expression.
+    fn write_operand(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.item_count() == 0 {
+            // An empty AND group is vacuously true, an empty OR/XOR group false.
+            let truthy = (self.mode == ConditionMode::AND) != self.negate;
+            return f.write_str(if truthy { "()" } else { "!()" });
+        }
+        if self.negate {
+            f.write_str("!(")?;
+            self.write_body(f)?;
+            return f.write_str(")");
+        }
+        if self.item_count() == 1 {
+            return self.write_body(f);
+        }
+        f.write_str("(")?;
+        self.write_body(f)?;
+        f.write_str(")")
+    }
+}
+
+impl fmt::Display for JsonNestedCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negate || self.item_count() == 0 {
+            self.write_operand(f)
+        } else {
+            self.write_body(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_options() {
+        let source = r#"name starts_with "bitcoin" with {case_insensitive: true, trim: true} && symbol in ["btc","bch"] with {case_insensitive: true}"#;
+        let condition = parse_condition(source).unwrap();
+        assert!(condition.rules[0].case_insensitive);
+        assert!(condition.rules[0].trim);
+        assert!(condition.rules[1].case_insensitive);
+        assert!(!condition.rules[1].trim);
+    }
+
+    #[test]
+    fn test_string_options_round_trip() {
+        let source = r#"name starts_with "bitcoin" with {case_insensitive: true, trim: true} && symbol in ["btc","bch"] with {case_insensitive: true}"#;
+        let printed = parse_condition(source).unwrap().to_string();
+        assert_eq!(printed, source);
+        assert_eq!(
+            serde_json::to_value(parse_condition(&printed).unwrap()).unwrap(),
+            serde_json::to_value(parse_condition(source).unwrap()).unwrap()
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_normalization_option_round_trip() {
+        let source = r#"symbol == "FI" with {case_insensitive: true, normalize: "nfkc"}"#;
+        let condition = parse_condition(source).unwrap();
+        assert_eq!(condition.rules[0].normalize, Some(Normalization::Nfkc));
+        assert_eq!(condition.to_string(), source);
+    }
+
+    #[test]
+    fn test_expression_fields() {
+        let source = "price_change_24h / price > 0.05 && abs(open - price) < 5 && max(open, price) == 100 && range.low + 10 + 0 between [99,101] && ((range.low - 5) * 2) / 4 <= 42.5 && (open + price) / 2 == 98";
+        let condition = parse_condition(source).unwrap();
+        assert_eq!(
+            serde_json::to_value(&condition.rules[0].field).unwrap(),
+            serde_json::json!({"$div": [{"$field": "price_change_24h"}, {"$field": "price"}]})
+        );
+        assert_eq!(
+            condition.rules[3].field,
+            JsonField::Expression(Expression::Add(vec![
+                Expression::field("range.low"),
+                10.0.into(),
+                0.0.into(),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_expression_round_trip() {
+        let source = "price_change_24h / price > 0.05 && abs(open - price) < 5 && max(open, price) == 100 && range.low + 10 + 0 between [99,101] && ((range.low - 5) * 2) / 4 <= 42.5 && (open + price) / 2 == 98";
+        let printed = parse_condition(source).unwrap().to_string();
+        assert_eq!(printed, source);
+        assert_eq!(
+            serde_json::to_value(parse_condition(&printed).unwrap()).unwrap(),
+            serde_json::to_value(parse_condition(source).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_expressions_print_as_dsl() {
+        let json = r#"{"rules": [
+            {"field": {"$div": [{"$field": "price"}, {"$sub": [{"$field": "open"}, 96]}]}, "operator": "greater_than", "value": 1},
+            {"field": "price", "operator": "less_than", "value": {"$mul": [{"$field": "avg_price"}, 2]}}
+        ]}"#;
+        let condition: JsonNestedCondition = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            serde_json::to_value(parse_condition(&condition.to_string()).unwrap()).unwrap(),
+            serde_json::to_value(&condition).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_precedence() {
+        let condition = parse_condition("open + price * 2 - -4 > 0").unwrap();
+        assert_eq!(
+            serde_json::to_value(&condition.rules[0].field).unwrap(),
+            serde_json::json!({"$sub": [{"$add": [{"$field": "open"}, {"$mul": [{"$field": "price"}, 2.0]}]}, -4.0]})
+        );
+    }
+
+    #[test]
+    fn test_minus_after_operand_subtracts() {
+        let condition = parse_condition("open -1 == 95 && open-1 == 95 && (open)-1 == 95").unwrap();
+        let expected = JsonField::Expression(Expression::Sub(
+            Box::new(Expression::field("open")),
+            Box::new(1.0.into()),
+        ));
+        assert_eq!(condition.rules[0].field, expected);
+        assert_eq!(condition.rules[1].field, expected);
+        assert_eq!(condition.rules[2].field, expected);
+    }
+
+    #[test]
+    fn test_minus_after_operator_negates() {
+        let condition = parse_condition("open greater_than -1 && open in [1, -2] && open > -1").unwrap();
+        assert_eq!(condition.rules[0].value, serde_json::json!(-1));
+        assert_eq!(condition.rules[1].value, serde_json::json!([1, -2]));
+        assert_eq!(condition.rules[2].value, serde_json::json!(-1));
+    }
+
+    #[test]
+    fn test_quoted_field_in_expression() {
+        let source = "`unit-price` * 2 > 1";
+        assert_eq!(parse_condition(source).unwrap().to_string(), source);
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        assert!(parse_condition("abs(open, price) > 1").is_err());
+        assert!(parse_condition("min() > 1").is_err());
+        assert!(parse_condition("avg(open) > 1").is_err());
+        let err = parse_condition("(open + ) > 1").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 9));
+    }
+}
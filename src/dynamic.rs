@@ -0,0 +1,161 @@
+//! Object-safe access to matchable values.
+//!
+//! [`Matchable`] is generic over the matched type, so a collection of
+//! matchable elements can't be handed out as `&dyn Matchable`. Elements are
+//! exposed as `&dyn DynMatchable` instead, which itself implements
+//! `Matchable` and can be evaluated like any other value.
+
+use std::any::Any;
+
+use crate::matchable::{FieldValue, Matchable};
+
+/// Object-safe counterpart of [`Matchable`], implemented for every matchable type.
+///
+/// Returned by [`Matchable::get_field_elements`] so that conditions can be
+/// evaluated against each element of a collection field. The methods carry a
+/// `dyn_` prefix so they never clash with [`Matchable`]'s when both traits
+/// are in scope:
+///
+/// ```rust
+/// use condition_matcher::*;
+///
+/// #[derive(MatchableDerive, PartialEq)]
+/// struct Part {
+///     sku: String,
+/// }
+///
+/// #[derive(MatchableDerive, PartialEq)]
+/// struct Order {
+///     id: u32,
+///     note: Option<String>,
+///     #[matchable(nested)]
+///     parts: Vec<Part>,
+/// }
+///
+/// let order = Order { id: 1, note: None, parts: vec![Part { sku: "A".into() }] };
+/// assert!(order.get_field("id").is_some());
+/// assert_eq!(order.get_field_elements(&["parts"]).map(|p| p.len()), Some(1));
+/// ```
+pub trait DynMatchable {
+    /// See [`Matchable::get_field`]
+    fn dyn_get_field(&self, field: &str) -> Option<&dyn Any>;
+    /// See [`Matchable::get_field_value`]
+    fn dyn_get_field_value(&self, field: &str) -> Option<FieldValue<'_>>;
+    /// See [`Matchable::get_field_path`]
+    fn dyn_get_field_path(&self, path: &[&str]) -> Option<&dyn Any>;
+    /// See [`Matchable::get_field_path_value`]
+    fn dyn_get_field_path_value(&self, path: &[&str]) -> Option<FieldValue<'_>>;
+    /// See [`Matchable::get_field_elements`]
+    fn dyn_get_field_elements(&self, path: &[&str]) -> Option<Vec<&dyn DynMatchable>>;
+    /// See [`Matchable::field_is_none`]
+    fn dyn_field_is_none(&self, path: &[&str]) -> Option<bool>;
+    /// See [`Matchable::field_is_empty`]
+    fn dyn_field_is_empty(&self, path: &[&str]) -> Option<bool>;
+    /// See [`Matchable::get_length`]
+    fn dyn_get_length(&self) -> Option<usize>;
+    /// See [`Matchable::is_empty`]
+    fn dyn_is_empty(&self) -> Option<bool>;
+    /// See [`Matchable::is_none`]
+    fn dyn_is_none(&self) -> bool;
+    /// See [`Matchable::type_name`]
+    fn dyn_type_name(&self) -> &str;
+}
+
+impl<T: Matchable> DynMatchable for T {
+    fn dyn_get_field(&self, field: &str) -> Option<&dyn Any> {
+        Matchable::get_field(self, field)
+    }
+
+    fn dyn_get_field_value(&self, field: &str) -> Option<FieldValue<'_>> {
+        Matchable::get_field_value(self, field)
+    }
+
+    fn dyn_get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
+        Matchable::get_field_path(self, path)
+    }
+
+    fn dyn_get_field_path_value(&self, path: &[&str]) -> Option<FieldValue<'_>> {
+        Matchable::get_field_path_value(self, path)
+    }
+
+    fn dyn_get_field_elements(&self, path: &[&str]) -> Option<Vec<&dyn DynMatchable>> {
+        Matchable::get_field_elements(self, path)
+    }
+
+    fn dyn_field_is_none(&self, path: &[&str]) -> Option<bool> {
+        Matchable::field_is_none(self, path)
+    }
+
+    fn dyn_field_is_empty(&self, path: &[&str]) -> Option<bool> {
+        Matchable::field_is_empty(self, path)
+    }
+
+    fn dyn_get_length(&self) -> Option<usize> {
+        Matchable::get_length(self)
+    }
+
+    fn dyn_is_empty(&self) -> Option<bool> {
+        Matchable::is_empty(self)
+    }
+
+    fn dyn_is_none(&self) -> bool {
+        Matchable::is_none(self)
+    }
+
+    fn dyn_type_name(&self) -> &str {
+        Matchable::type_name(self)
+    }
+}
+
+/// Elements are only equal to themselves.
+impl PartialEq for dyn DynMatchable + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::addr_eq(self, other)
+    }
+}
+
+impl<'a> Matchable for &'a (dyn DynMatchable + 'a) {
+    fn get_length(&self) -> Option<usize> {
+        DynMatchable::dyn_get_length(*self)
+    }
+
+    fn get_field(&self, field: &str) -> Option<&dyn Any> {
+        DynMatchable::dyn_get_field(*self, field)
+    }
+
+    fn get_field_value(&self, field: &str) -> Option<FieldValue<'_>> {
+        DynMatchable::dyn_get_field_value(*self, field)
+    }
+
+    fn get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
+        DynMatchable::dyn_get_field_path(*self, path)
+    }
+
+    fn get_field_path_value(&self, path: &[&str]) -> Option<FieldValue<'_>> {
+        DynMatchable::dyn_get_field_path_value(*self, path)
+    }
+
+    fn get_field_elements(&self, path: &[&str]) -> Option<Vec<&dyn DynMatchable>> {
+        DynMatchable::dyn_get_field_elements(*self, path)
+    }
+
+    fn field_is_none(&self, path: &[&str]) -> Option<bool> {
+        DynMatchable::dyn_field_is_none(*self, path)
+    }
+
+    fn field_is_empty(&self, path: &[&str]) -> Option<bool> {
+        DynMatchable::dyn_field_is_empty(*self, path)
+    }
+
+    fn type_name(&self) -> &str {
+        DynMatchable::dyn_type_name(*self)
+    }
+
+    fn is_empty(&self) -> Option<bool> {
+        DynMatchable::dyn_is_empty(*self)
+    }
+
+    fn is_none(&self) -> bool {
+        DynMatchable::dyn_is_none(*self)
+    }
+}
@@ -1,7 +1,11 @@
 use std::fmt;
 
+#[cfg(feature = "json_condition")]
+use crate::condition::ConditionOperator;
+
 /// Errors that can occur during condition matching
 #[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum MatchError {
     /// The specified field was not found on the type
     FieldNotFound {
@@ -36,6 +40,15 @@ pub enum MatchError {
         path: Vec<String>,
         failed_at: String,
     },
+    /// The operand can't be used with the operator
+    InvalidOperand {
+        operator: String,
+        message: String,
+    },
+    /// An arithmetic expression divided by zero
+    DivisionByZero {
+        expression: String,
+    },
 }
 
 impl fmt::Display for MatchError {
@@ -63,8 +76,91 @@ impl fmt::Display for MatchError {
             MatchError::NestedFieldNotFound { path, failed_at } => {
                 write!(f, "Nested field not found at '{}' in path {:?}", failed_at, path)
             }
+            MatchError::InvalidOperand { operator, message } => {
+                write!(f, "Invalid operand for '{}': {}", operator, message)
+            }
+            MatchError::DivisionByZero { expression } => {
+                write!(f, "Division by zero in '{}'", expression)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+/// Error produced when a condition expression can't be parsed.
+#[cfg(feature = "json_condition")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// What went wrong
+    pub message: String,
+    /// 1-based line of the offending input
+    pub line: usize,
+    /// 1-based column of the offending input
+    pub column: usize,
+}
+
+#[cfg(feature = "json_condition")]
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {}, column {}", self.message, self.line, self.column)
+    }
+}
+
+#[cfg(feature = "json_condition")]
+impl std::error::Error for ParseError {}
+
+/// A rule that doesn't fit the [`Schema`](crate::Schema) it was validated against.
+#[cfg(feature = "json_condition")]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// The field (or a segment of its path) isn't in the schema
+    UnknownField { field: String },
+    /// The operator can't be applied to the field's type
+    UnsupportedOperator {
+        field: String,
+        operator: ConditionOperator,
+        kind: String,
+    },
+    /// The operand has the wrong type for the field or operator
+    InvalidValue {
+        field: String,
+        operator: ConditionOperator,
+        kind: String,
+        value: serde_json::Value,
+    },
+}
+
+#[cfg(feature = "json_condition")]
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::UnknownField { field } => {
+                write!(f, "Unknown field '{}'", field)
+            }
+            ValidationError::UnsupportedOperator { field, operator, kind } => {
+                write!(
+                    f,
+                    "Operator {:?} is not supported for field '{}' of type {}",
+                    operator, field, kind
+                )
+            }
+            ValidationError::InvalidValue {
+                field,
+                operator,
+                kind,
+                value,
+            } => {
+                write!(
+                    f,
+                    "Invalid value {} for {:?} on field '{}' of type {}",
+                    value, operator, field, kind
+                )
+            }
         }
     }
 }
 
-impl std::error::Error for MatchError {}
\ No newline at end of file
+#[cfg(feature = "json_condition")]
+impl std::error::Error for ValidationError {}
@@ -0,0 +1,268 @@
+//! Collection operators: `ContainsAny`, `ContainsAll`, `AnyElement` and `AllElements`.
+
+use std::any::Any;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::condition::ConditionOperator;
+#[cfg(feature = "json_condition")]
+use crate::{
+    condition::JsonNestedCondition, dynamic::DynMatchable, error::MatchError, matchable::Matchable,
+    result::ConditionResult,
+};
+
+#[cfg(feature = "json_condition")]
+use super::comparison::any_type_name;
+use super::comparison::{CompareOptions, compare_any_values};
+#[cfg(feature = "json_condition")]
+use super::json::{JsonEvaluator, compare_json_to_any};
+#[cfg(feature = "json_condition")]
+use super::prepared::PreparedCondition;
+
+/// The scalar items of a `Vec`, `&'static` slice, `HashSet` or JSON array,
+/// along with the collection's display string.
+pub(crate) fn collection_items(value: &dyn Any) -> Option<(Vec<&dyn Any>, String)> {
+    macro_rules! sequence {
+        ($($t:ty),*) => {
+            $(
+                if let Some(items) = value.downcast_ref::<Vec<$t>>() {
+                    return Some((items.iter().map(|v| v as &dyn Any).collect(), format!("{:?}", items)));
+                }
+                if let Some(items) = value.downcast_ref::<&[$t]>() {
+                    return Some((items.iter().map(|v| v as &dyn Any).collect(), format!("{:?}", items)));
+                }
+            )*
+        };
+    }
+    macro_rules! set {
+        ($($t:ty),*) => {
+            $(
+                if let Some(items) = value.downcast_ref::<HashSet<$t>>() {
+                    return Some((items.iter().map(|v| v as &dyn Any).collect(), format!("{:?}", items)));
+                }
+            )*
+        };
+    }
+
+    sequence!(
+        i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char,
+        String, &str
+    );
+    set!(
+        i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, bool, char, String, &str
+    );
+    #[cfg(feature = "decimal")]
+    sequence!(rust_decimal::Decimal);
+    #[cfg(feature = "chrono")]
+    sequence!(
+        chrono::DateTime<chrono::Utc>,
+        chrono::NaiveDate,
+        chrono::Duration
+    );
+    #[cfg(feature = "json_condition")]
+    {
+        if let Some(serde_json::Value::Array(items)) = value.downcast_ref::<serde_json::Value>() {
+            return Some((
+                items.iter().map(|v| v as &dyn Any).collect(),
+                serde_json::to_string(items).ok()?,
+            ));
+        }
+        sequence!(serde_json::Value);
+    }
+    None
+}
+
+/// Whether a string, collection, string-keyed map or JSON value is empty;
+/// JSON null counts as empty. `None` for other types.
+pub(crate) fn any_is_empty(value: &dyn Any) -> Option<bool> {
+    macro_rules! map {
+        ($($t:ty),*) => {
+            $(
+                if let Some(map) = value.downcast_ref::<HashMap<String, $t>>() {
+                    return Some(map.is_empty());
+                }
+                if let Some(map) = value.downcast_ref::<BTreeMap<String, $t>>() {
+                    return Some(map.is_empty());
+                }
+            )*
+        };
+    }
+
+    if let Some(s) = value.downcast_ref::<String>() {
+        return Some(s.is_empty());
+    }
+    if let Some(s) = value.downcast_ref::<&str>() {
+        return Some(str::is_empty(s));
+    }
+    #[cfg(feature = "json_condition")]
+    if let Some(json) = value.downcast_ref::<serde_json::Value>() {
+        return match json {
+            serde_json::Value::Null => Some(true),
+            serde_json::Value::String(s) => Some(s.is_empty()),
+            serde_json::Value::Array(items) => Some(items.is_empty()),
+            serde_json::Value::Object(map) => Some(map.is_empty()),
+            _ => None,
+        };
+    }
+    if let Some((items, _)) = collection_items(value) {
+        return Some(items.is_empty());
+    }
+    map!(
+        i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char,
+        String, &str
+    );
+    #[cfg(feature = "json_condition")]
+    map!(serde_json::Value);
+    None
+}
+
+/// Evaluate `ContainsAny`/`ContainsAll` between two collections, comparing
+/// items as `Equals` would.
+/// Returns (passed, actual_string, expected_string).
+pub fn compare_collections(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+    options: CompareOptions,
+) -> (bool, Option<String>, Option<String>) {
+    let (Some((items, actual_str)), Some((wanted, expected_str))) =
+        (collection_items(actual), collection_items(expected))
+    else {
+        return (false, None, None);
+    };
+    let contains = |w: &dyn Any| {
+        items
+            .iter()
+            .any(|i| compare_any_values(*i, w, &ConditionOperator::Equals, options).0)
+    };
+    (
+        quantify(operator, wanted.iter().map(|w| contains(*w))),
+        Some(actual_str),
+        Some(expected_str),
+    )
+}
+
+/// Evaluate `ContainsAny`/`ContainsAll` between a collection and a JSON array operand.
+/// Returns (passed, actual_string, expected_string).
+#[cfg(feature = "json_condition")]
+pub fn compare_collection_to_json(
+    actual: &dyn Any,
+    expected: &serde_json::Value,
+    operator: &ConditionOperator,
+    options: CompareOptions,
+) -> (bool, Option<String>, Option<String>) {
+    let (Some((items, actual_str)), Some(wanted)) = (collection_items(actual), expected.as_array())
+    else {
+        return (false, None, None);
+    };
+    let contains = |w: &serde_json::Value| {
+        items
+            .iter()
+            .any(|i| compare_json_to_any(*i, w, &ConditionOperator::Equals, options).0)
+    };
+    (
+        quantify(operator, wanted.iter().map(contains)),
+        Some(actual_str),
+        Some(expected.to_string()),
+    )
+}
+
+/// Combine per-item results: any for `ContainsAny`/`AnyElement`, all for
+/// `ContainsAll`/`AllElements`.
+fn quantify(operator: &ConditionOperator, mut results: impl Iterator<Item = bool>) -> bool {
+    match operator {
+        ConditionOperator::ContainsAny | ConditionOperator::AnyElement => results.any(|r| r),
+        ConditionOperator::ContainsAll | ConditionOperator::AllElements => results.all(|r| r),
+        _ => false,
+    }
+}
+
+/// Evaluate `AnyElement`/`AllElements` by matching `condition` against each
+/// element of the collection at `path`.
+///
+/// Elements come from [`Matchable::get_field_elements`], or from a JSON array
+/// field. Returns the result and the number of elements, or `None` if the
+/// field isn't such a collection. `AllElements` holds for an empty collection.
+#[cfg(feature = "json_condition")]
+pub fn evaluate_elements<T: Matchable>(
+    value: &T,
+    path: &[&str],
+    operator: &ConditionOperator,
+    condition: &JsonNestedCondition,
+    prepared: &PreparedCondition,
+) -> Option<(bool, usize)> {
+    let field = value.get_field_path_value(path);
+    let elements: Vec<&dyn DynMatchable> = match value.get_field_elements(path) {
+        Some(elements) => elements,
+        None => field
+            .as_ref()?
+            .as_any()
+            .downcast_ref::<serde_json::Value>()?
+            .as_array()?
+            .iter()
+            .map(|v| v as &dyn DynMatchable)
+            .collect(),
+    };
+    let matches = |element: &&dyn DynMatchable| {
+        JsonEvaluator::evaluate_prepared(condition, prepared, element).matched
+    };
+    Some((quantify(operator, elements.iter().map(matches)), elements.len()))
+}
+
+/// Evaluate a programmatic `AnyElement`/`AllElements` condition, whose
+/// operand is a [`JsonNestedCondition`].
+#[cfg(feature = "json_condition")]
+pub fn evaluate_element_condition<T: Matchable>(
+    value: &T,
+    path: &[&str],
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> ConditionResult {
+    let description = format!("field '{}' {:?}", path.join("."), operator);
+    let Some(condition) = expected.downcast_ref::<JsonNestedCondition>() else {
+        return ConditionResult {
+            passed: false,
+            description,
+            actual_value: None,
+            expected_value: None,
+            error: Some(MatchError::InvalidOperand {
+                operator: format!("{:?}", operator),
+                message: format!(
+                    "expected a JsonNestedCondition, got '{}'",
+                    any_type_name(expected)
+                ),
+            }),
+        };
+    };
+    let expected_value = serde_json::to_string(condition).ok();
+
+    let prepared = PreparedCondition::new(condition);
+    match evaluate_elements(value, path, operator, condition, &prepared) {
+        Some((passed, count)) => ConditionResult {
+            passed,
+            description,
+            actual_value: Some(format!("{} elements", count)),
+            expected_value,
+            error: None,
+        },
+        None => {
+            let error = match value.get_field_path_value(path) {
+                Some(actual) => MatchError::TypeMismatch {
+                    field: path.join("."),
+                    expected: "collection of matchable elements".to_string(),
+                    actual: any_type_name(actual.as_any()).to_string(),
+                },
+                None => MatchError::FieldNotFound {
+                    field: path.join("."),
+                    type_name: value.type_name().to_string(),
+                },
+            };
+            ConditionResult {
+                passed: false,
+                description,
+                actual_value: None,
+                expected_value,
+                error: Some(error),
+            }
+        }
+    }
+}
@@ -1,9 +1,33 @@
 //! Comparison utilities for evaluating conditions.
 
 use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt;
+use std::hash::Hash;
+use std::ops::{Bound, Range, RangeBounds, RangeInclusive};
 
-use crate::condition::ConditionOperator;
+use crate::comparator;
+#[cfg(feature = "chrono")]
+use crate::temporal;
+use crate::condition::{ConditionOperator, StringOptions, Tolerance};
+use crate::error::MatchError;
+
+use super::collection::{collection_items, compare_collections};
+
+#[cfg(all(feature = "json_condition", feature = "chrono"))]
+use super::json::compare_json_to_temporal;
+#[cfg(all(feature = "json_condition", feature = "decimal"))]
+use super::json::json_to_decimal;
+
+/// How values are compared, as set on a condition.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CompareOptions {
+    /// Allowed difference for float equality
+    pub tolerance: Tolerance,
+    /// How strings are prepared before comparing
+    pub strings: StringOptions,
+}
 
 /// Compare two numeric values with an operator.
 pub fn compare_numeric<N: PartialOrd>(actual: N, expected: N, operator: &ConditionOperator) -> bool {
@@ -18,13 +42,46 @@ pub fn compare_numeric<N: PartialOrd>(actual: N, expected: N, operator: &Conditi
     }
 }
 
-/// Compare two type-erased values.
+/// Compare two type-erased values, with floats and strings compared as set
+/// in `options`.
 /// Returns (passed, actual_string, expected_string).
 pub fn compare_any_values(
     actual: &dyn Any,
     expected: &dyn Any,
     operator: &ConditionOperator,
+    options: CompareOptions,
 ) -> (bool, Option<String>, Option<String>) {
+    // Set membership
+    if matches!(operator, ConditionOperator::In | ConditionOperator::NotIn) {
+        return compare_membership(actual, expected, operator, &options.strings);
+    }
+
+    // Range checks
+    if *operator == ConditionOperator::Between {
+        return compare_range(actual, expected, &options.strings);
+    }
+
+    // Collection containment
+    if matches!(operator, ConditionOperator::ContainsAny | ConditionOperator::ContainsAll) {
+        return compare_collections(actual, expected, operator, options);
+    }
+
+    // Float equality within a tolerance, for numbers of any type
+    if options.tolerance != Tolerance::default()
+        && matches!(operator, ConditionOperator::Equals | ConditionOperator::NotEquals)
+        && let (Some(a), Some(e)) = (Numeric::from_any(actual), Numeric::from_any(expected))
+        && let Some(passed) = a.compare(e, operator, options.tolerance)
+    {
+        return (passed, Some(a.to_string()), Some(e.to_string()));
+    }
+
+    // JSON values (e.g. fields of a serde_json::Value document)
+    #[cfg(feature = "json_condition")]
+    if let Some(json) = actual.downcast_ref::<serde_json::Value>() {
+        return comparator::compare_json_to_custom(json, expected, operator)
+            .unwrap_or_else(|| compare_json_value_to_any(json, expected, operator, options));
+    }
+
     // Integer types
     if let Some(result) = try_compare::<i8>(actual, expected, operator) {
         return result;
@@ -73,13 +130,34 @@ pub fn compare_any_values(
         return result;
     }
 
+    // Numbers of different types, compared without loss
+    if let Some(result) = try_compare_mixed_numeric(actual, expected, operator) {
+        return result;
+    }
+
+    // Decimal, compared exactly
+    #[cfg(feature = "decimal")]
+    if let Some(result) = try_compare::<rust_decimal::Decimal>(actual, expected, operator) {
+        return result;
+    }
+
+    // Dates, times and durations
+    #[cfg(feature = "chrono")]
+    if let Some(result) = try_compare::<chrono::DateTime<chrono::Utc>>(actual, expected, operator)
+        .or_else(|| try_compare::<chrono::NaiveDate>(actual, expected, operator))
+        .or_else(|| try_compare::<chrono::Duration>(actual, expected, operator))
+        .or_else(|| try_compare_temporal_str(actual, expected, operator))
+    {
+        return result;
+    }
+
     // Boolean
     if let Some(result) = try_compare::<bool>(actual, expected, operator) {
         return result;
     }
 
     // String types with string operations
-    if let Some(result) = try_compare_strings(actual, expected, operator) {
+    if let Some(result) = try_compare_strings(actual, expected, operator, &options.strings) {
         return result;
     }
 
@@ -88,10 +166,542 @@ pub fn compare_any_values(
         return result;
     }
 
+    // Registered custom types
+    if let Some(result) = comparator::compare_custom(actual, expected, operator) {
+        return result;
+    }
+
     // No match found
     (false, None, None)
 }
 
+/// The error to report for a field whose value couldn't be compared with the
+/// expected value: `UnsupportedOperator` if both have the same type,
+/// `TypeMismatch` otherwise.
+pub fn comparison_error(
+    field: &str,
+    actual: &dyn Any,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> MatchError {
+    if actual.type_id() == expected.type_id() {
+        MatchError::UnsupportedOperator {
+            operator: format!("{:?}", operator),
+            context: format!("field '{}' of type '{}'", field, any_type_name(actual)),
+        }
+    } else {
+        MatchError::TypeMismatch {
+            field: field.to_string(),
+            expected: any_type_name(expected).to_string(),
+            actual: any_type_name(actual).to_string(),
+        }
+    }
+}
+
+/// Name of a type-erased value's type, for the types the evaluators know.
+pub fn any_type_name(value: &dyn Any) -> &'static str {
+    macro_rules! known {
+        ($($t:ty),*) => {
+            $(
+                if value.is::<$t>() {
+                    return std::any::type_name::<$t>();
+                }
+            )*
+        };
+    }
+    known!(
+        i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char,
+        String, &str
+    );
+    #[cfg(feature = "json_condition")]
+    known!(serde_json::Value);
+    #[cfg(feature = "decimal")]
+    known!(rust_decimal::Decimal);
+    #[cfg(feature = "chrono")]
+    known!(chrono::DateTime<chrono::Utc>, chrono::NaiveDate, chrono::Duration);
+    "unknown"
+}
+
+/// A primitive number widened without loss.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Numeric {
+    Int(i128),
+    /// Only for values above `i128::MAX`
+    UInt(u128),
+    Float(f64),
+}
+
+/// 2^127 as a float; the smallest float above `i128::MAX`.
+const TWO_POW_127: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+
+/// Widen a primitive or JSON number to `f64`.
+#[cfg(feature = "json_condition")]
+pub(crate) fn numeric_f64(value: &dyn Any) -> Option<f64> {
+    Numeric::from_any(value).map(Numeric::as_f64)
+}
+
+impl Numeric {
+    /// Widen a primitive number, or a JSON number, to a `Numeric`.
+    pub(crate) fn from_any(value: &dyn Any) -> Option<Self> {
+        macro_rules! int {
+            ($($t:ty),*) => {
+                $(
+                    if let Some(v) = value.downcast_ref::<$t>() {
+                        return Some(Numeric::Int(*v as i128));
+                    }
+                )*
+            };
+        }
+        int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+        if let Some(v) = value.downcast_ref::<u128>() {
+            return Some(match i128::try_from(*v) {
+                Ok(i) => Numeric::Int(i),
+                Err(_) => Numeric::UInt(*v),
+            });
+        }
+        if let Some(v) = value.downcast_ref::<f64>() {
+            return Some(Numeric::Float(*v));
+        }
+        if let Some(v) = value.downcast_ref::<f32>() {
+            return Some(Numeric::Float(*v as f64));
+        }
+        #[cfg(feature = "json_condition")]
+        if let Some(serde_json::Value::Number(n)) = value.downcast_ref::<serde_json::Value>() {
+            return Some(Self::from_json(n));
+        }
+        None
+    }
+
+    /// Widen a JSON number, keeping integers as integers.
+    #[cfg(feature = "json_condition")]
+    pub(crate) fn from_json(n: &serde_json::Number) -> Self {
+        if let Some(i) = n.as_i64() {
+            Numeric::Int(i as i128)
+        } else if let Some(u) = n.as_u64() {
+            Numeric::Int(u as i128)
+        } else {
+            Numeric::Float(n.as_f64().unwrap_or(f64::NAN))
+        }
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Numeric::Int(v) => v as f64,
+            Numeric::UInt(v) => v as f64,
+            Numeric::Float(v) => v,
+        }
+    }
+
+    /// Compare with an operator. Floats are equal within `tolerance`;
+    /// integers are always compared exactly.
+    /// Returns `None` for operators that don't apply to numbers.
+    pub(crate) fn compare(
+        self,
+        other: Self,
+        operator: &ConditionOperator,
+        tolerance: Tolerance,
+    ) -> Option<bool> {
+        let is_float = matches!(self, Numeric::Float(_)) || matches!(other, Numeric::Float(_));
+        let loose = is_float && tolerance != Tolerance::default();
+        let equal = || self == other || (loose && tolerance.equal(self.as_f64(), other.as_f64()));
+        Some(match operator {
+            ConditionOperator::Equals => equal(),
+            ConditionOperator::NotEquals => !equal(),
+            ConditionOperator::GreaterThan => self > other,
+            ConditionOperator::LessThan => self < other,
+            ConditionOperator::GreaterThanOrEqual => self >= other,
+            ConditionOperator::LessThanOrEqual => self <= other,
+            _ => return None,
+        })
+    }
+}
+
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        use Numeric::*;
+
+        match (*self, *other) {
+            (Int(a), Int(b)) => Some(a.cmp(&b)),
+            (UInt(a), UInt(b)) => Some(a.cmp(&b)),
+            (UInt(_), Int(_)) => Some(Ordering::Greater),
+            (Int(_), UInt(_)) => Some(Ordering::Less),
+            (Float(a), Float(b)) => a.partial_cmp(&b),
+            (Int(a), Float(b)) => compare_int_float(a, b),
+            (UInt(a), Float(b)) => compare_uint_float(a, b),
+            (Float(a), Int(b)) => compare_int_float(b, a).map(Ordering::reverse),
+            (Float(a), UInt(b)) => compare_uint_float(b, a).map(Ordering::reverse),
+        }
+    }
+}
+
+impl fmt::Display for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Numeric::Int(v) => write!(f, "{}", v),
+            Numeric::UInt(v) => write!(f, "{}", v),
+            Numeric::Float(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Compare an integer with a float exactly, by their integral parts first.
+fn compare_int_float(a: i128, b: f64) -> Option<Ordering> {
+    if b.is_nan() {
+        return None;
+    }
+    if b >= TWO_POW_127 {
+        return Some(Ordering::Less);
+    }
+    if b < -TWO_POW_127 {
+        return Some(Ordering::Greater);
+    }
+    let whole = b.trunc();
+    Some(a.cmp(&(whole as i128)).then(whole.partial_cmp(&b)?))
+}
+
+/// Compare an integer above `i128::MAX` with a float exactly.
+fn compare_uint_float(a: u128, b: f64) -> Option<Ordering> {
+    if b.is_nan() {
+        return None;
+    }
+    if b < TWO_POW_127 {
+        return Some(Ordering::Greater);
+    }
+    if b >= 2.0 * TWO_POW_127 {
+        return Some(Ordering::Less);
+    }
+    // Floats this large are integral, so the conversion is exact
+    Some(a.cmp(&(b as u128)))
+}
+
+/// Compare numbers of different primitive types, e.g. a `u64` field with a
+/// `u32` operand. Integers are compared exactly, including `i128` and `u128`.
+fn try_compare_mixed_numeric(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    let a = Numeric::from_any(actual)?;
+    let e = Numeric::from_any(expected)?;
+    let passed = a.compare(e, operator, Tolerance::default())?;
+    Some((passed, Some(a.to_string()), Some(e.to_string())))
+}
+
+/// Check whether a value is contained in a `Vec`, `&'static` slice or `HashSet`,
+/// for the `In` and `NotIn` operators. Numbers of different types are compared
+/// without loss. Strings are prepared with `strings` first.
+/// Returns (passed, actual_string, expected_string).
+pub fn compare_membership(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+    strings: &StringOptions,
+) -> (bool, Option<String>, Option<String>) {
+    let found = try_string_membership(actual, expected, strings)
+        .or_else(|| try_membership::<i8>(actual, expected))
+        .or_else(|| try_membership::<i16>(actual, expected))
+        .or_else(|| try_membership::<i32>(actual, expected))
+        .or_else(|| try_membership::<i64>(actual, expected))
+        .or_else(|| try_membership::<i128>(actual, expected))
+        .or_else(|| try_membership::<isize>(actual, expected))
+        .or_else(|| try_membership::<u8>(actual, expected))
+        .or_else(|| try_membership::<u16>(actual, expected))
+        .or_else(|| try_membership::<u32>(actual, expected))
+        .or_else(|| try_membership::<u64>(actual, expected))
+        .or_else(|| try_membership::<u128>(actual, expected))
+        .or_else(|| try_membership::<usize>(actual, expected))
+        .or_else(|| try_membership::<bool>(actual, expected))
+        .or_else(|| try_membership::<char>(actual, expected))
+        .or_else(|| try_decimal_membership(actual, expected))
+        .or_else(|| try_temporal_membership(actual, expected))
+        .or_else(|| try_slice_membership::<f32>(actual, expected))
+        .or_else(|| try_slice_membership::<f64>(actual, expected))
+        .or_else(|| try_numeric_membership(actual, expected));
+
+    match found {
+        Some((found, actual_str, expected_str)) => {
+            let passed = match operator {
+                ConditionOperator::In => found,
+                ConditionOperator::NotIn => !found,
+                _ => false,
+            };
+            (passed, Some(actual_str), Some(expected_str))
+        }
+        None => (false, None, None),
+    }
+}
+
+#[cfg(feature = "decimal")]
+fn try_decimal_membership(actual: &dyn Any, expected: &dyn Any) -> Option<(bool, String, String)> {
+    try_membership::<rust_decimal::Decimal>(actual, expected)
+}
+
+#[cfg(not(feature = "decimal"))]
+fn try_decimal_membership(_actual: &dyn Any, _expected: &dyn Any) -> Option<(bool, String, String)> {
+    None
+}
+
+#[cfg(feature = "chrono")]
+fn try_temporal_membership(actual: &dyn Any, expected: &dyn Any) -> Option<(bool, String, String)> {
+    try_membership::<chrono::DateTime<chrono::Utc>>(actual, expected)
+        .or_else(|| try_membership::<chrono::NaiveDate>(actual, expected))
+        .or_else(|| try_membership::<chrono::Duration>(actual, expected))
+}
+
+#[cfg(not(feature = "chrono"))]
+fn try_temporal_membership(_actual: &dyn Any, _expected: &dyn Any) -> Option<(bool, String, String)> {
+    None
+}
+
+fn try_membership<T: Eq + Hash + fmt::Debug + fmt::Display + 'static>(
+    actual: &dyn Any,
+    expected: &dyn Any,
+) -> Option<(bool, String, String)> {
+    let a = actual.downcast_ref::<T>()?;
+    if let Some(set) = expected.downcast_ref::<HashSet<T>>() {
+        return Some((set.contains(a), a.to_string(), format!("{:?}", set)));
+    }
+    try_slice_membership::<T>(actual, expected)
+}
+
+/// Membership for types that can't be hashed; only `Vec` and slices are supported.
+fn try_slice_membership<T: PartialEq + fmt::Debug + fmt::Display + 'static>(
+    actual: &dyn Any,
+    expected: &dyn Any,
+) -> Option<(bool, String, String)> {
+    let a = actual.downcast_ref::<T>()?;
+    let items: &[T] = expected
+        .downcast_ref::<Vec<T>>()
+        .map(|v| v.as_slice())
+        .or_else(|| expected.downcast_ref::<&[T]>().copied())?;
+    Some((items.contains(a), a.to_string(), format!("{:?}", items)))
+}
+
+/// Membership for a number in a collection of another numeric type, e.g. a
+/// `u32` field in a `Vec<i64>`, compared as `Equals` would.
+fn try_numeric_membership(actual: &dyn Any, expected: &dyn Any) -> Option<(bool, String, String)> {
+    let a = Numeric::from_any(actual)?;
+    let (items, expected_str) = collection_items(expected)?;
+    let items = items
+        .into_iter()
+        .map(Numeric::from_any)
+        .collect::<Option<Vec<_>>>()?;
+    Some((items.contains(&a), a.to_string(), expected_str))
+}
+
+fn try_string_membership(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    strings: &StringOptions,
+) -> Option<(bool, String, String)> {
+    let a: &str = actual
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| actual.downcast_ref::<&str>().copied())?;
+
+    // Sets are hashed on the original strings, so prepared strings are
+    // compared member by member
+    let exact = *strings == StringOptions::default();
+    let prepared = strings.apply(a);
+    let matches = |s: &str| strings.apply(s) == prepared;
+
+    let (found, expected_str) = if let Some(set) = expected.downcast_ref::<HashSet<String>>() {
+        let found = if exact { set.contains(a) } else { set.iter().any(|s| matches(s)) };
+        (found, format!("{:?}", set))
+    } else if let Some(set) = expected.downcast_ref::<HashSet<&str>>() {
+        let found = if exact { set.contains(a) } else { set.iter().any(|s| matches(s)) };
+        (found, format!("{:?}", set))
+    } else if let Some(items) = expected.downcast_ref::<Vec<String>>() {
+        (items.iter().any(|s| matches(s)), format!("{:?}", items))
+    } else if let Some(items) = expected.downcast_ref::<Vec<&str>>() {
+        (items.iter().any(|s| matches(s)), format!("{:?}", items))
+    } else if let Some(items) = expected.downcast_ref::<&[&str]>() {
+        (items.iter().any(|s| matches(s)), format!("{:?}", items))
+    } else {
+        return None;
+    };
+    Some((found, a.to_string(), expected_str))
+}
+
+/// Check whether a value lies within a `RangeInclusive`, `Range` or
+/// `(Bound, Bound)` of the same type, for the `Between` operator. Strings are
+/// prepared with `strings` first.
+/// Returns (passed, actual_string, expected_string).
+pub fn compare_range(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    strings: &StringOptions,
+) -> (bool, Option<String>, Option<String>) {
+    let result = try_string_range(actual, expected, strings)
+        .or_else(|| try_range::<i8>(actual, expected))
+        .or_else(|| try_range::<i16>(actual, expected))
+        .or_else(|| try_range::<i32>(actual, expected))
+        .or_else(|| try_range::<i64>(actual, expected))
+        .or_else(|| try_range::<i128>(actual, expected))
+        .or_else(|| try_range::<isize>(actual, expected))
+        .or_else(|| try_range::<u8>(actual, expected))
+        .or_else(|| try_range::<u16>(actual, expected))
+        .or_else(|| try_range::<u32>(actual, expected))
+        .or_else(|| try_range::<u64>(actual, expected))
+        .or_else(|| try_range::<u128>(actual, expected))
+        .or_else(|| try_range::<usize>(actual, expected))
+        .or_else(|| try_range::<f32>(actual, expected))
+        .or_else(|| try_range::<f64>(actual, expected))
+        .or_else(|| try_range::<char>(actual, expected))
+        .or_else(|| try_decimal_range(actual, expected))
+        .or_else(|| try_temporal_range(actual, expected));
+
+    match result {
+        Some((passed, actual_str, expected_str)) => (passed, Some(actual_str), Some(expected_str)),
+        None => (false, None, None),
+    }
+}
+
+#[cfg(feature = "decimal")]
+fn try_decimal_range(actual: &dyn Any, expected: &dyn Any) -> Option<(bool, String, String)> {
+    try_range::<rust_decimal::Decimal>(actual, expected)
+}
+
+#[cfg(not(feature = "decimal"))]
+fn try_decimal_range(_actual: &dyn Any, _expected: &dyn Any) -> Option<(bool, String, String)> {
+    None
+}
+
+#[cfg(feature = "chrono")]
+fn try_temporal_range(actual: &dyn Any, expected: &dyn Any) -> Option<(bool, String, String)> {
+    try_range::<chrono::DateTime<chrono::Utc>>(actual, expected)
+        .or_else(|| try_range::<chrono::NaiveDate>(actual, expected))
+        .or_else(|| try_range::<chrono::Duration>(actual, expected))
+}
+
+#[cfg(not(feature = "chrono"))]
+fn try_temporal_range(_actual: &dyn Any, _expected: &dyn Any) -> Option<(bool, String, String)> {
+    None
+}
+
+fn try_range<T: PartialOrd + fmt::Display + 'static>(
+    actual: &dyn Any,
+    expected: &dyn Any,
+) -> Option<(bool, String, String)> {
+    let a = actual.downcast_ref::<T>()?;
+    let bounds: (Bound<&T>, Bound<&T>) = if let Some(r) = expected.downcast_ref::<RangeInclusive<T>>() {
+        (r.start_bound(), r.end_bound())
+    } else if let Some(r) = expected.downcast_ref::<Range<T>>() {
+        (r.start_bound(), r.end_bound())
+    } else if let Some((start, end)) = expected.downcast_ref::<(Bound<T>, Bound<T>)>() {
+        (start.as_ref(), end.as_ref())
+    } else {
+        return None;
+    };
+    Some((bounds.contains(a), a.to_string(), format_bounds(bounds)))
+}
+
+fn try_string_range(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    strings: &StringOptions,
+) -> Option<(bool, String, String)> {
+    let a: &str = actual
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| actual.downcast_ref::<&str>().copied())?;
+
+    let bounds: (Bound<&str>, Bound<&str>) = if let Some(r) = expected.downcast_ref::<RangeInclusive<&str>>() {
+        (Bound::Included(*r.start()), Bound::Included(*r.end()))
+    } else if let Some(r) = expected.downcast_ref::<Range<&str>>() {
+        (Bound::Included(r.start), Bound::Excluded(r.end))
+    } else if let Some(r) = expected.downcast_ref::<RangeInclusive<String>>() {
+        (Bound::Included(r.start().as_str()), Bound::Included(r.end().as_str()))
+    } else if let Some(r) = expected.downcast_ref::<Range<String>>() {
+        (Bound::Included(r.start.as_str()), Bound::Excluded(r.end.as_str()))
+    } else {
+        return None;
+    };
+    Some(string_range_contains(a, bounds, strings))
+}
+
+/// Check a string against string bounds, preparing all three with `strings`.
+/// Returns (passed, actual_string, expected_string).
+pub(crate) fn string_range_contains(
+    actual: &str,
+    bounds: (Bound<&str>, Bound<&str>),
+    strings: &StringOptions,
+) -> (bool, String, String) {
+    let (lo, hi) = (bounds.0.map(|s| strings.apply(s)), bounds.1.map(|s| strings.apply(s)));
+    let prepared: (Bound<&str>, Bound<&str>) = (lo.as_ref().map(|s| s.as_ref()), hi.as_ref().map(|s| s.as_ref()));
+    (
+        RangeBounds::<str>::contains(&prepared, strings.apply(actual).as_ref()),
+        actual.to_string(),
+        format_bounds(bounds),
+    )
+}
+
+/// Format a pair of bounds in interval notation, e.g. `[1, 10)`.
+pub fn format_bounds<T: fmt::Display + ?Sized>(bounds: (Bound<&T>, Bound<&T>)) -> String {
+    let start = match bounds.0 {
+        Bound::Included(v) => format!("[{}", v),
+        Bound::Excluded(v) => format!("({}", v),
+        Bound::Unbounded => "(-inf".to_string(),
+    };
+    let end = match bounds.1 {
+        Bound::Included(v) => format!("{}]", v),
+        Bound::Excluded(v) => format!("{})", v),
+        Bound::Unbounded => "inf)".to_string(),
+    };
+    format!("{}, {}", start, end)
+}
+
+/// Compare a JSON value (as the actual side) against a programmatic expected value.
+#[cfg(feature = "json_condition")]
+fn compare_json_value_to_any(
+    actual: &serde_json::Value,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+    options: CompareOptions,
+) -> (bool, Option<String>, Option<String>) {
+    // Decimal operands are compared exactly, without going through f64
+    #[cfg(feature = "decimal")]
+    if let Some(e) = expected.downcast_ref::<rust_decimal::Decimal>() {
+        return match json_to_decimal(actual) {
+            Some(a) => (
+                compare_numeric(a, *e, operator),
+                Some(a.to_string()),
+                Some(e.to_string()),
+            ),
+            None => (false, None, Some(e.to_string())),
+        };
+    }
+
+    // Timestamps and durations written as strings in the document
+    #[cfg(feature = "chrono")]
+    if let Some(result) = compare_json_to_temporal(actual, expected, operator) {
+        return result;
+    }
+
+    match actual {
+        serde_json::Value::String(s) => compare_any_values(s, expected, operator, options),
+        serde_json::Value::Bool(b) => compare_any_values(b, expected, operator, options),
+        serde_json::Value::Number(n) => match Numeric::from_any(expected) {
+            Some(e) => {
+                let a = Numeric::from_json(n);
+                match a.compare(e, operator, options.tolerance) {
+                    Some(passed) => (passed, Some(a.to_string()), Some(e.to_string())),
+                    None => (false, None, None),
+                }
+            }
+            None => (false, None, None),
+        },
+        _ => (false, None, None),
+    }
+}
+
 fn try_compare<T: PartialOrd + PartialEq + fmt::Display + 'static>(
     actual: &dyn Any,
     expected: &dyn Any,
@@ -113,11 +723,38 @@ fn try_compare<T: PartialOrd + PartialEq + fmt::Display + 'static>(
     }
 }
 
-/// Try to compare string values with string-specific operators.
+/// Compare a `DateTime<Utc>`, `NaiveDate` or `Duration` field against a string
+/// operand such as `"now-7d"`, `"2024-05-01"` or `"30m"`.
+#[cfg(feature = "chrono")]
+fn try_compare_temporal_str(
+    actual: &dyn Any,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    let text = expected
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| expected.downcast_ref::<&str>().copied())?;
+
+    if actual.is::<chrono::DateTime<chrono::Utc>>() {
+        return try_compare::<chrono::DateTime<chrono::Utc>>(actual, &temporal::parse_datetime(text)?, operator);
+    }
+    if actual.is::<chrono::NaiveDate>() {
+        return try_compare::<chrono::NaiveDate>(actual, &temporal::parse_date(text)?, operator);
+    }
+    if actual.is::<chrono::Duration>() {
+        return try_compare::<chrono::Duration>(actual, &temporal::parse_duration(text)?, operator);
+    }
+    None
+}
+
+/// Try to compare string values with string-specific operators, preparing
+/// both sides with `strings` first.
 pub fn try_compare_strings(
     actual: &dyn Any,
     expected: &dyn Any,
     operator: &ConditionOperator,
+    strings: &StringOptions,
 ) -> Option<(bool, Option<String>, Option<String>)> {
     // Get the actual string
     let actual_str: Option<&str> = actual
@@ -125,6 +762,18 @@ pub fn try_compare_strings(
         .map(|s| s.as_str())
         .or_else(|| actual.downcast_ref::<&str>().copied());
 
+    // Reuse a pattern compiled ahead of time
+    #[cfg(feature = "regex")]
+    if *operator == ConditionOperator::Regex
+        && let Some(re) = expected.downcast_ref::<regex::Regex>()
+    {
+        let pattern = Some(re.as_str().to_string());
+        return Some(match actual_str {
+            Some(a) => (regex_matches(re, a, strings), Some(a.to_string()), pattern),
+            None => (false, None, pattern),
+        });
+    }
+
     // Get the expected string
     let expected_str: Option<&str> = expected
         .downcast_ref::<String>()
@@ -133,30 +782,67 @@ pub fn try_compare_strings(
 
     match (actual_str, expected_str) {
         (Some(a), Some(e)) => {
-            let passed = match operator {
-                ConditionOperator::Equals => a == e,
-                ConditionOperator::NotEquals => a != e,
-                ConditionOperator::Contains => a.contains(e),
-                ConditionOperator::NotContains => !a.contains(e),
-                ConditionOperator::StartsWith => a.starts_with(e),
-                ConditionOperator::EndsWith => a.ends_with(e),
-                ConditionOperator::GreaterThan => a > e,
-                ConditionOperator::LessThan => a < e,
-                ConditionOperator::GreaterThanOrEqual => a >= e,
-                ConditionOperator::LessThanOrEqual => a <= e,
-                ConditionOperator::IsEmpty => a.is_empty(),
-                ConditionOperator::IsNotEmpty => !a.is_empty(),
-                #[cfg(feature = "regex")]
-                ConditionOperator::Regex => regex::Regex::new(e)
-                    .map(|re| re.is_match(a))
-                    .unwrap_or(false),
-                #[cfg(not(feature = "regex"))]
-                ConditionOperator::Regex => false,
-                _ => return None,
-            };
+            let passed = compare_strings(a, e, operator, strings)?;
             Some((passed, Some(a.to_string()), Some(e.to_string())))
         }
         _ => None,
     }
 }
 
+/// Compare two strings with a string operator after preparing both with
+/// `strings`. Returns `None` for operators that don't apply to strings.
+pub(crate) fn compare_strings(
+    actual: &str,
+    expected: &str,
+    operator: &ConditionOperator,
+    strings: &StringOptions,
+) -> Option<bool> {
+    #[cfg(feature = "regex")]
+    if *operator == ConditionOperator::Regex {
+        return Some(
+            compile_regex(expected, strings.case_insensitive)
+                .is_ok_and(|re| regex_matches(&re, actual, strings)),
+        );
+    }
+
+    let (a, e) = (strings.apply(actual), strings.apply(expected));
+    let (a, e) = (a.as_ref(), e.as_ref());
+    Some(match operator {
+        ConditionOperator::Equals => a == e,
+        ConditionOperator::NotEquals => a != e,
+        ConditionOperator::Contains => a.contains(e),
+        ConditionOperator::NotContains => !a.contains(e),
+        ConditionOperator::StartsWith => a.starts_with(e),
+        ConditionOperator::EndsWith => a.ends_with(e),
+        ConditionOperator::GreaterThan => a > e,
+        ConditionOperator::LessThan => a < e,
+        ConditionOperator::GreaterThanOrEqual => a >= e,
+        ConditionOperator::LessThanOrEqual => a <= e,
+        ConditionOperator::IsEmpty => a.is_empty(),
+        ConditionOperator::IsNotEmpty => !a.is_empty(),
+        #[cfg(not(feature = "regex"))]
+        ConditionOperator::Regex => false,
+        _ => return None,
+    })
+}
+
+/// Match a compiled pattern against a string prepared with `strings`.
+///
+/// Case is left to the pattern, which is compiled case-insensitively when
+/// the options ask for it.
+#[cfg(feature = "regex")]
+pub(crate) fn regex_matches(re: &regex::Regex, actual: &str, strings: &StringOptions) -> bool {
+    re.is_match(&strings.case_sensitive().apply(actual))
+}
+
+/// Compile a regex pattern, mapping failures to [`MatchError::RegexError`].
+#[cfg(feature = "regex")]
+pub fn compile_regex(pattern: &str, case_insensitive: bool) -> Result<regex::Regex, MatchError> {
+    regex::RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+        .map_err(|e| MatchError::RegexError {
+            pattern: pattern.to_string(),
+            message: e.to_string(),
+        })
+}
@@ -3,34 +3,61 @@
 use std::any::Any;
 
 use crate::{
-    condition::ConditionOperator, error::MatchError, matchable::Matchable, result::ConditionResult,
+    condition::ConditionOperator,
+    error::MatchError,
+    matchable::{FieldValue, Matchable},
+    result::ConditionResult,
 };
 
-use super::comparison::compare_any_values;
+#[cfg(feature = "json_condition")]
+use super::collection::evaluate_element_condition;
+#[cfg(feature = "json_condition")]
+use super::json::compare_json_to_any;
+use super::collection::any_is_empty;
+use super::comparison::{any_type_name, compare_any_values, comparison_error, CompareOptions};
 
 /// Evaluator for single field comparisons.
 pub struct FieldEvaluator;
 
 impl FieldEvaluator {
-    /// Evaluate a field condition against a Matchable value.
+    /// Evaluate a field condition against a Matchable value, comparing with
+    /// the condition's tolerance and string options.
     pub fn evaluate<T: Matchable>(
         value: &T,
         field: &str,
         expected: &dyn Any,
         operator: &ConditionOperator,
+        options: CompareOptions,
     ) -> ConditionResult {
-        match value.get_field(field) {
+        #[cfg(feature = "json_condition")]
+        if operator.is_quantifier() {
+            return evaluate_element_condition(value, &[field], expected, operator);
+        }
+        if operator.is_presence() || operator.is_emptiness() {
+            let path: Vec<&str> = field.split('.').collect();
+            return presence_result(value, &path, operator, format!("field '{}' {:?}", field, operator));
+        }
+
+        match value.get_field_value(field) {
             Some(actual) => {
+                let actual = actual.as_any();
                 let (passed, actual_str, expected_str) =
-                    compare_any_values(actual, expected, operator);
+                    compare_any_values(actual, expected, operator, options);
+                // A comparison that applied always reports the actual value
+                let error = actual_str
+                    .is_none()
+                    .then(|| comparison_error(field, actual, expected, operator));
                 ConditionResult {
                     passed,
                     description: format!("field '{}' {:?}", field, operator),
                     actual_value: actual_str,
                     expected_value: expected_str,
-                    error: None,
+                    error,
                 }
             }
+            None if holds_none(value, field) => {
+                none_result(format!("field '{}' {:?}", field, operator))
+            }
             None => ConditionResult {
                 passed: false,
                 description: format!("field '{}' {:?}", field, operator),
@@ -43,5 +70,198 @@ impl FieldEvaluator {
             },
         }
     }
+
+    /// Evaluate a condition comparing `field` with another field of the
+    /// same value. Either name may be a dotted path.
+    pub fn evaluate_reference<T: Matchable>(
+        value: &T,
+        field: &str,
+        other: &str,
+        operator: &ConditionOperator,
+        options: CompareOptions,
+    ) -> ConditionResult {
+        let description = format!("field '{}' {:?} field '{}'", field, operator, other);
+        let missing = |name: &str| {
+            if holds_none(value, name) {
+                return none_result(description.clone());
+            }
+            ConditionResult {
+                passed: false,
+                description: description.clone(),
+                actual_value: None,
+                expected_value: None,
+                error: Some(MatchError::FieldNotFound {
+                    field: name.to_string(),
+                    type_name: value.type_name().to_string(),
+                }),
+            }
+        };
+        let Some(actual) = resolve_field(value, field) else {
+            return missing(field);
+        };
+        let Some(expected) = resolve_field(value, other) else {
+            return missing(other);
+        };
+
+        let (actual, expected) = (actual.as_any(), expected.as_any());
+        let (passed, actual_str, expected_str) =
+            compare_field_values(actual, expected, operator, options);
+        let error = actual_str
+            .is_none()
+            .then(|| comparison_error(field, actual, expected, operator));
+        ConditionResult {
+            passed,
+            description,
+            actual_value: actual_str,
+            expected_value: expected_str,
+            error,
+        }
+    }
+}
+
+/// Evaluate `IsNone`, `IsSome`, `Exists` or `NotExists` at `path`.
+///
+/// Returns whether the check passed and the field's state (`"some"`, `"none"`
+/// or `"missing"`), or `None` for `IsNone`/`IsSome` on a missing field.
+pub(crate) fn check_presence<T: Matchable>(
+    value: &T,
+    path: &[&str],
+    operator: &ConditionOperator,
+) -> Option<(bool, &'static str)> {
+    let state = value.field_is_none(path);
+    let passed = match operator {
+        ConditionOperator::Exists => state.is_some(),
+        ConditionOperator::NotExists => state.is_none(),
+        ConditionOperator::IsNone => state?,
+        ConditionOperator::IsSome => !state?,
+        _ => return None,
+    };
+    let label = match state {
+        Some(false) => "some",
+        Some(true) => "none",
+        None => "missing",
+    };
+    Some((passed, label))
 }
 
+/// Whether the string, collection or map at `path` is empty, as reported by
+/// [`Matchable::field_is_empty`]. Otherwise a None field is empty, and the
+/// field value's runtime type or [`Matchable::get_field_elements`] decides.
+pub(crate) fn check_emptiness<T: Matchable>(value: &T, path: &[&str]) -> Result<bool, MatchError> {
+    if let Some(empty) = value.field_is_empty(path) {
+        return Ok(empty);
+    }
+    match value.field_is_none(path) {
+        None => {
+            return Err(MatchError::FieldNotFound {
+                field: path.join("."),
+                type_name: value.type_name().to_string(),
+            });
+        }
+        Some(true) => return Ok(true),
+        Some(false) => {}
+    }
+    let field = value.get_field_path_value(path);
+    if let Some(empty) = field.as_ref().and_then(|f| any_is_empty(f.as_any())) {
+        return Ok(empty);
+    }
+    if let Some(elements) = value.get_field_elements(path) {
+        return Ok(elements.is_empty());
+    }
+    Err(MatchError::TypeMismatch {
+        field: path.join("."),
+        expected: "string or collection".to_string(),
+        actual: field.map_or("unknown", |f| any_type_name(f.as_any())).to_string(),
+    })
+}
+
+/// Build the result of a presence or emptiness check, reporting a missing
+/// field as an error for all but `Exists`/`NotExists`.
+pub(crate) fn presence_result<T: Matchable>(
+    value: &T,
+    path: &[&str],
+    operator: &ConditionOperator,
+    description: String,
+) -> ConditionResult {
+    if operator.is_emptiness() {
+        return match check_emptiness(value, path) {
+            Ok(empty) => ConditionResult {
+                passed: empty == (*operator == ConditionOperator::IsEmpty),
+                description,
+                actual_value: Some(if empty { "empty" } else { "not empty" }.to_string()),
+                expected_value: None,
+                error: None,
+            },
+            Err(error) => ConditionResult {
+                passed: false,
+                description,
+                actual_value: None,
+                expected_value: None,
+                error: Some(error),
+            },
+        };
+    }
+    match check_presence(value, path, operator) {
+        Some((passed, state)) => ConditionResult {
+            passed,
+            description,
+            actual_value: Some(state.to_string()),
+            expected_value: None,
+            error: None,
+        },
+        None => ConditionResult {
+            passed: false,
+            description,
+            actual_value: None,
+            expected_value: None,
+            error: Some(MatchError::FieldNotFound {
+                field: path.join("."),
+                type_name: value.type_name().to_string(),
+            }),
+        },
+    }
+}
+
+/// Whether a field that didn't resolve to a value holds `None`, according to
+/// [`Matchable::field_is_none`]. `field` may be a dotted path.
+pub(crate) fn holds_none<T: Matchable>(value: &T, field: &str) -> bool {
+    let path: Vec<&str> = field.split('.').collect();
+    value.field_is_none(&path) == Some(true)
+}
+
+/// The result of comparing a field that holds `None`: the comparison doesn't
+/// match, but the field exists, so it isn't an error.
+pub(crate) fn none_result(description: String) -> ConditionResult {
+    ConditionResult {
+        passed: false,
+        description,
+        actual_value: Some("none".to_string()),
+        expected_value: None,
+        error: None,
+    }
+}
+
+/// Look up a field by name, or by path if it contains dots.
+pub(crate) fn resolve_field<'v, T: Matchable>(value: &'v T, field: &str) -> Option<FieldValue<'v>> {
+    if field.contains('.') {
+        let path: Vec<&str> = field.split('.').collect();
+        value.get_field_path_value(&path)
+    } else {
+        value.get_field_value(field)
+    }
+}
+
+/// Compare two field values, where the second stands in for the expected
+/// value. Fields of JSON documents are compared as JSON operands.
+pub(crate) fn compare_field_values(
+    actual: &dyn Any,
+    other: &dyn Any,
+    operator: &ConditionOperator,
+    options: CompareOptions,
+) -> (bool, Option<String>, Option<String>) {
+    #[cfg(feature = "json_condition")]
+    if let Some(json) = other.downcast_ref::<serde_json::Value>() {
+        return compare_json_to_any(actual, json, operator, options);
+    }
+    compare_any_values(actual, other, operator, options)
+}
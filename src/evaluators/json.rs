@@ -1,13 +1,31 @@
 //! JSON evaluator for evaluating JSON-based conditions.
 
 use std::any::Any;
+use std::ops::{Bound, RangeBounds};
 
+#[cfg(feature = "chrono")]
+use crate::temporal;
 use crate::{
-    condition::{ConditionMode, ConditionOperator, JsonCondition, JsonNestedCondition},
+    comparator,
+    condition::{
+        ConditionMode, ConditionOperator, JsonCondition, JsonField, JsonNestedCondition,
+        StringOptions,
+    },
+    error::MatchError,
     matchable::Matchable,
     result::{JsonConditionResult, JsonEvalResult},
 };
 
+#[cfg(any(feature = "decimal", feature = "chrono"))]
+use super::comparison::compare_numeric;
+use super::collection::{compare_collection_to_json, evaluate_elements};
+use super::comparison::{
+    compare_any_values, compare_strings, format_bounds, string_range_contains, CompareOptions,
+    Numeric,
+};
+use super::field::{check_emptiness, check_presence, compare_field_values, holds_none, resolve_field};
+use super::prepared::{PreparedCondition, PreparedRule, ValueSet};
+
 /// Evaluator for JSON-based conditions.
 pub struct JsonEvaluator;
 
@@ -16,196 +34,666 @@ impl JsonEvaluator {
     pub fn evaluate<T: Matchable>(
         condition: &JsonNestedCondition,
         value: &T,
+    ) -> JsonEvalResult {
+        Self::evaluate_prepared(condition, &PreparedCondition::new(condition), value)
+    }
+
+    /// Evaluate a JsonNestedCondition using operand data prepared ahead of time.
+    pub fn evaluate_prepared<T: Matchable>(
+        condition: &JsonNestedCondition,
+        prepared: &PreparedCondition,
+        value: &T,
     ) -> JsonEvalResult {
         let mut details = Vec::new();
-        Self::evaluate_recursive(condition, value, &mut details)
+        Self::evaluate_recursive(condition, Some(prepared), value, &mut details)
     }
 
     fn evaluate_recursive<T: Matchable>(
         group: &JsonNestedCondition,
+        prepared: Option<&PreparedCondition>,
         value: &T,
         details: &mut Vec<JsonConditionResult>,
     ) -> JsonEvalResult {
         let mut flags = Vec::new();
 
         // Evaluate all rules at this level
-        for rule in &group.rules {
-            let result = Self::evaluate_rule(rule, value);
+        for (i, rule) in group.rules.iter().enumerate() {
+            let prepared_rule = prepared.and_then(|p| p.rules.get(i));
+            let result = Self::evaluate_rule(rule, prepared_rule, value);
             flags.push(result.passed);
             details.push(result);
         }
 
         // Evaluate nested groups recursively
-        for nested in &group.nested {
-            let nested_result = Self::evaluate_recursive(nested, value, details);
+        for (i, nested) in group.nested.iter().enumerate() {
+            let prepared_nested = prepared.and_then(|p| p.nested.get(i));
+            let nested_result = Self::evaluate_recursive(nested, prepared_nested, value, details);
             flags.push(nested_result.matched);
         }
 
-        let matched = combine_results(&flags, group.mode);
+        let matched = combine_results(&flags, group.mode) != group.negate;
         JsonEvalResult {
             matched,
             details: details.clone(),
         }
     }
 
-    fn evaluate_rule<T: Matchable>(rule: &JsonCondition, value: &T) -> JsonConditionResult {
-        let field = &rule.field;
+    fn evaluate_rule<T: Matchable>(
+        rule: &JsonCondition,
+        prepared: Option<&PreparedRule>,
+        value: &T,
+    ) -> JsonConditionResult {
+        if let Some(err) = prepared.and_then(PreparedRule::error) {
+            return JsonConditionResult {
+                passed: false,
+                field: rule.field.to_string(),
+                operator: rule.operator,
+                expected: rule.value.clone(),
+                actual: None,
+                error: Some(err.to_string()),
+            };
+        }
+
+        let field = match &rule.field {
+            JsonField::Path(path) => path,
+            JsonField::Expression(_) => return Self::evaluate_expression(rule, prepared, value),
+        };
 
         // Support dotted paths like "user.age" by splitting on '.'
         let path_segments: Vec<&str> = field.split('.').collect();
 
+        if rule.operator.is_quantifier() {
+            return Self::evaluate_quantifier(rule, prepared, value, &path_segments);
+        }
+        if rule.operator.is_presence() {
+            return Self::evaluate_presence(rule, value, field, &path_segments);
+        }
+        if rule.operator.is_emptiness() {
+            return Self::evaluate_emptiness(rule, value, field, &path_segments);
+        }
+        if let Some(other) = rule.field_reference() {
+            return Self::evaluate_reference(rule, prepared, value, field, other);
+        }
+        let computed = match prepared {
+            Some(p) => p.expression().is_some(),
+            None => rule.value_expression().is_some(),
+        };
+        if computed {
+            return Self::evaluate_expression(rule, prepared, value);
+        }
+
         // Try to resolve the field value
         let actual_value = if path_segments.len() == 1 {
-            value.get_field(field)
+            value.get_field_value(field)
         } else {
-            value.get_field_path(&path_segments)
+            value.get_field_path_value(&path_segments)
         };
 
         match actual_value {
             Some(actual) => {
-                let (passed, actual_str, _expected_str) =
-                    compare_json_to_any(actual, &rule.value, &rule.operator);
+                let actual = actual.as_any();
+                let options = compare_options(rule, prepared);
+                let (passed, actual_str, _expected_str) = prepared
+                    .and_then(|p| p.compare(actual, &rule.operator))
+                    .unwrap_or_else(|| {
+                        compare_json_to_any(actual, &rule.value, &rule.operator, options)
+                    });
                 JsonConditionResult {
                     passed,
                     field: field.clone(),
                     operator: rule.operator,
                     expected: rule.value.clone(),
-                    actual: actual_str
-                        .clone()
-                        .and_then(|s| serde_json::from_str(&format!("\"{}\"", s)).ok())
-                        .or_else(|| {
-                            actual_str.and_then(|s| s.parse::<f64>().ok().map(serde_json::Value::from))
-                        }),
+                    actual: actual_json(actual_str),
                     error: None,
                 }
             }
-            None => JsonConditionResult {
-                passed: false,
-                field: field.clone(),
-                operator: rule.operator,
-                expected: rule.value.clone(),
-                actual: None,
-                error: Some(format!("Field '{}' not found", field)),
-            },
+            None => {
+                let none = value.field_is_none(&path_segments) == Some(true);
+                JsonConditionResult {
+                    passed: false,
+                    field: field.clone(),
+                    operator: rule.operator,
+                    expected: rule.value.clone(),
+                    actual: none.then_some(serde_json::Value::Null),
+                    error: (!none).then(|| format!("Field '{}' not found", field)),
+                }
+            }
         }
     }
 }
 
-/// Extract a numeric value as f64 from a type-erased Any reference.
-pub fn extract_as_f64(actual: &dyn Any) -> Option<f64> {
-    if let Some(v) = actual.downcast_ref::<f64>() {
-        return Some(*v);
-    }
-    if let Some(v) = actual.downcast_ref::<f32>() {
-        return Some(*v as f64);
-    }
-    if let Some(v) = actual.downcast_ref::<i64>() {
-        return Some(*v as f64);
-    }
-    if let Some(v) = actual.downcast_ref::<i32>() {
-        return Some(*v as f64);
-    }
-    if let Some(v) = actual.downcast_ref::<i16>() {
-        return Some(*v as f64);
-    }
-    if let Some(v) = actual.downcast_ref::<i8>() {
-        return Some(*v as f64);
+impl JsonEvaluator {
+    /// Evaluate an `any_element`/`all_elements` rule, whose operand is a
+    /// condition group matched against each element of the field.
+    fn evaluate_quantifier<T: Matchable>(
+        rule: &JsonCondition,
+        prepared: Option<&PreparedRule>,
+        value: &T,
+        path: &[&str],
+    ) -> JsonConditionResult {
+        let mut result = JsonConditionResult {
+            passed: false,
+            field: rule.field.to_string(),
+            operator: rule.operator,
+            expected: rule.value.clone(),
+            actual: None,
+            error: None,
+        };
+
+        let parsed;
+        let (condition, prepared) = match prepared.and_then(PreparedRule::elements) {
+            Some(elements) => elements,
+            None => match PreparedRule::parse_elements(&rule.value) {
+                Ok(elements) => {
+                    parsed = elements;
+                    (&parsed.0, &parsed.1)
+                }
+                Err(err) => {
+                    result.error = Some(err.to_string());
+                    return result;
+                }
+            },
+        };
+
+        match evaluate_elements(value, path, &rule.operator, condition, prepared) {
+            Some((passed, count)) => {
+                result.passed = passed;
+                result.actual = Some(serde_json::Value::from(count));
+            }
+            None if value.get_field_path_value(path).is_some() => {
+                result.error = Some(format!("Field '{}' is not a collection", rule.field));
+            }
+            None => result.error = Some(format!("Field '{}' not found", rule.field)),
+        }
+        result
     }
-    if let Some(v) = actual.downcast_ref::<u64>() {
-        return Some(*v as f64);
+
+    /// Evaluate `is_none`, `is_some`, `exists` or `not_exists`; the actual
+    /// value is the field's state, `"some"`, `"none"` or `"missing"`.
+    fn evaluate_presence<T: Matchable>(
+        rule: &JsonCondition,
+        value: &T,
+        field: &str,
+        path: &[&str],
+    ) -> JsonConditionResult {
+        let (passed, actual, error) = match check_presence(value, path, &rule.operator) {
+            Some((passed, state)) => (passed, Some(serde_json::Value::from(state)), None),
+            None => (false, None, Some(format!("Field '{}' not found", field))),
+        };
+        JsonConditionResult {
+            passed,
+            field: field.to_string(),
+            operator: rule.operator,
+            expected: rule.value.clone(),
+            actual,
+            error,
+        }
     }
-    if let Some(v) = actual.downcast_ref::<u32>() {
-        return Some(*v as f64);
+
+    /// Evaluate `is_empty` or `is_not_empty` on a string, collection, object
+    /// or None field; the actual value is `"empty"` or `"not empty"`.
+    fn evaluate_emptiness<T: Matchable>(
+        rule: &JsonCondition,
+        value: &T,
+        field: &str,
+        path: &[&str],
+    ) -> JsonConditionResult {
+        let (passed, actual, error) = match check_emptiness(value, path) {
+            Ok(empty) => (
+                empty == (rule.operator == ConditionOperator::IsEmpty),
+                Some(serde_json::Value::from(if empty { "empty" } else { "not empty" })),
+                None,
+            ),
+            Err(MatchError::FieldNotFound { .. }) => {
+                (false, None, Some(format!("Field '{}' not found", field)))
+            }
+            Err(_) => (
+                false,
+                None,
+                Some(format!("Field '{}' is not a string or collection", field)),
+            ),
+        };
+        JsonConditionResult {
+            passed,
+            field: field.to_string(),
+            operator: rule.operator,
+            expected: rule.value.clone(),
+            actual,
+            error,
+        }
     }
-    if let Some(v) = actual.downcast_ref::<u16>() {
-        return Some(*v as f64);
+
+    /// Evaluate a rule whose operand is `{"$field": "name"}`, comparing the
+    /// field with another field of the same value.
+    fn evaluate_reference<T: Matchable>(
+        rule: &JsonCondition,
+        prepared: Option<&PreparedRule>,
+        value: &T,
+        field: &str,
+        other: &str,
+    ) -> JsonConditionResult {
+        let mut result = JsonConditionResult {
+            passed: false,
+            field: field.to_string(),
+            operator: rule.operator,
+            expected: rule.value.clone(),
+            actual: None,
+            error: None,
+        };
+        let Some(actual) = resolve_field(value, field) else {
+            return missing_field(result, value, field);
+        };
+        let Some(expected) = resolve_field(value, other) else {
+            return missing_field(result, value, other);
+        };
+
+        let (passed, actual_str, _expected_str) = compare_field_values(
+            actual.as_any(),
+            expected.as_any(),
+            &rule.operator,
+            compare_options(rule, prepared),
+        );
+        result.passed = passed;
+        result.actual = actual_json(actual_str);
+        result
     }
-    if let Some(v) = actual.downcast_ref::<u8>() {
-        return Some(*v as f64);
+
+    /// Evaluate a rule whose field or operand is an arithmetic expression,
+    /// comparing the computed numbers.
+    fn evaluate_expression<T: Matchable>(
+        rule: &JsonCondition,
+        prepared: Option<&PreparedRule>,
+        value: &T,
+    ) -> JsonConditionResult {
+        let mut result = JsonConditionResult {
+            passed: false,
+            field: rule.field.to_string(),
+            operator: rule.operator,
+            expected: rule.value.clone(),
+            actual: None,
+            error: None,
+        };
+
+        let computed;
+        let field;
+        let actual: &dyn Any = match &rule.field {
+            JsonField::Expression(expression) => match expression.evaluate(value) {
+                Ok(n) => {
+                    computed = n;
+                    &computed
+                }
+                Err(MatchError::FieldNotFound { field, .. }) => {
+                    return missing_field(result, value, &field);
+                }
+                Err(err) => {
+                    result.error = Some(err.to_string());
+                    return result;
+                }
+            },
+            JsonField::Path(path) => match resolve_field(value, path) {
+                Some(v) => {
+                    field = v;
+                    field.as_any()
+                }
+                None => return missing_field(result, value, path),
+            },
+        };
+
+        let options = compare_options(rule, prepared);
+        let parsed;
+        let expression = match prepared.and_then(PreparedRule::expression) {
+            Some(expression) => Some(expression),
+            None => {
+                parsed = rule.value_expression();
+                parsed.as_ref().map(Result::as_ref)
+            }
+        };
+        let (passed, actual_str, _expected_str) = match expression {
+            Some(Err(err)) => {
+                result.error = Some(err.to_string());
+                return result;
+            }
+            Some(Ok(expression)) => match expression.evaluate(value) {
+                Ok(expected) => compare_any_values(actual, &expected, &rule.operator, options),
+                Err(MatchError::FieldNotFound { field, .. }) => {
+                    return missing_field(result, value, &field);
+                }
+                Err(err) => {
+                    result.error = Some(err.to_string());
+                    return result;
+                }
+            },
+            None => compare_json_to_any(actual, &rule.value, &rule.operator, options),
+        };
+        result.passed = passed;
+        result.actual = actual_json(actual_str);
+        result
     }
-    if let Some(v) = actual.downcast_ref::<isize>() {
-        return Some(*v as f64);
+}
+
+/// Complete the result of a rule whose `field` didn't resolve: a field that
+/// holds `None` doesn't match, and any other field is reported as missing.
+fn missing_field<T: Matchable>(
+    mut result: JsonConditionResult,
+    value: &T,
+    field: &str,
+) -> JsonConditionResult {
+    if holds_none(value, field) {
+        result.actual = Some(serde_json::Value::Null);
+    } else {
+        result.error = Some(format!("Field '{}' not found", field));
     }
-    if let Some(v) = actual.downcast_ref::<usize>() {
-        return Some(*v as f64);
+    result
+}
+
+/// The tolerance and string options a rule is compared with.
+fn compare_options(rule: &JsonCondition, prepared: Option<&PreparedRule>) -> CompareOptions {
+    CompareOptions {
+        tolerance: prepared
+            .map(PreparedRule::tolerance)
+            .or_else(|| rule.float_tolerance())
+            .unwrap_or_default(),
+        strings: rule.string_options(),
     }
-    None
+}
+
+/// Convert a compared value's display string back into JSON for the result.
+fn actual_json(actual_str: Option<String>) -> Option<serde_json::Value> {
+    actual_str
+        .clone()
+        .and_then(|s| serde_json::from_str(&format!("\"{}\"", s)).ok())
+        .or_else(|| actual_str.and_then(|s| s.parse::<f64>().ok().map(serde_json::Value::from)))
+}
+
+/// Parse a JSON number or numeric string into a `Decimal` without going
+/// through `f64`.
+#[cfg(feature = "decimal")]
+pub fn json_to_decimal(value: &serde_json::Value) -> Option<rust_decimal::Decimal> {
+    use std::str::FromStr;
+
+    let text = match value {
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.trim().to_string(),
+        _ => return None,
+    };
+    rust_decimal::Decimal::from_str(&text)
+        .or_else(|_| rust_decimal::Decimal::from_scientific(&text))
+        .ok()
+}
+
+/// Extract a string slice from a type-erased Any reference.
+pub fn extract_as_str(actual: &dyn Any) -> Option<&str> {
+    actual
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| actual.downcast_ref::<&str>().copied())
+        .or_else(|| {
+            actual
+                .downcast_ref::<serde_json::Value>()
+                .and_then(|v| v.as_str())
+        })
+}
+
+/// Extract a boolean from a type-erased Any reference.
+pub fn extract_as_bool(actual: &dyn Any) -> Option<bool> {
+    actual.downcast_ref::<bool>().copied().or_else(|| {
+        actual
+            .downcast_ref::<serde_json::Value>()
+            .and_then(|v| v.as_bool())
+    })
 }
 
 /// Compare a JSON value against a type-erased Any reference.
+///
+/// Integers are compared exactly; floats and strings are compared as set in
+/// `options`.
 pub fn compare_json_to_any(
     actual: &dyn Any,
     expected: &serde_json::Value,
     operator: &ConditionOperator,
+    options: CompareOptions,
 ) -> (bool, Option<String>, Option<String>) {
-    // Numeric comparison
-    if let Some(exp_f64) = expected.as_f64() {
-        if let Some(act_f64) = extract_as_f64(actual) {
-            let passed = match operator {
-                ConditionOperator::Equals => (act_f64 - exp_f64).abs() < f64::EPSILON,
-                ConditionOperator::NotEquals => (act_f64 - exp_f64).abs() >= f64::EPSILON,
-                ConditionOperator::GreaterThan => act_f64 > exp_f64,
-                ConditionOperator::LessThan => act_f64 < exp_f64,
-                ConditionOperator::GreaterThanOrEqual => act_f64 >= exp_f64,
-                ConditionOperator::LessThanOrEqual => act_f64 <= exp_f64,
-                _ => false,
-            };
-            return (passed, Some(act_f64.to_string()), Some(exp_f64.to_string()));
-        }
+    // Set membership
+    if matches!(operator, ConditionOperator::In | ConditionOperator::NotIn) {
+        return match ValueSet::from_json(expected, options.strings) {
+            Some(set) => set.compare(actual, operator),
+            None => (false, None, None),
+        };
+    }
+
+    // Range checks
+    if *operator == ConditionOperator::Between {
+        return compare_json_range(actual, expected, &options.strings);
+    }
+
+    // Collection containment
+    if matches!(operator, ConditionOperator::ContainsAny | ConditionOperator::ContainsAll) {
+        return compare_collection_to_json(actual, expected, operator, options);
+    }
+
+    // Decimal comparison, exact for both JSON numbers and numeric strings
+    #[cfg(feature = "decimal")]
+    if let Some(act) = actual.downcast_ref::<rust_decimal::Decimal>()
+        && let Some(exp) = json_to_decimal(expected)
+    {
+        return compare_parsed(act, &exp, operator);
+    }
+
+    // Dates, times and durations
+    #[cfg(feature = "chrono")]
+    if let Some(result) = compare_temporal_to_json(actual, expected, operator) {
+        return result;
+    }
+
+    // Numeric comparison, exact for integers
+    if let serde_json::Value::Number(n) = expected
+        && let Some(act) = Numeric::from_any(actual)
+    {
+        let exp = Numeric::from_json(n);
+        let passed = act.compare(exp, operator, options.tolerance).unwrap_or(false);
+        return (passed, Some(act.to_string()), Some(exp.to_string()));
     }
 
     // String comparison
-    if let Some(exp_str) = expected.as_str() {
-        let act_str: Option<&str> = actual
-            .downcast_ref::<String>()
-            .map(|s| s.as_str())
-            .or_else(|| actual.downcast_ref::<&str>().copied());
-
-        if let Some(a) = act_str {
-            let passed = match operator {
-                ConditionOperator::Equals => a == exp_str,
-                ConditionOperator::NotEquals => a != exp_str,
-                ConditionOperator::Contains => a.contains(exp_str),
-                ConditionOperator::NotContains => !a.contains(exp_str),
-                ConditionOperator::StartsWith => a.starts_with(exp_str),
-                ConditionOperator::EndsWith => a.ends_with(exp_str),
-                ConditionOperator::GreaterThan => a > exp_str,
-                ConditionOperator::LessThan => a < exp_str,
-                ConditionOperator::GreaterThanOrEqual => a >= exp_str,
-                ConditionOperator::LessThanOrEqual => a <= exp_str,
-                ConditionOperator::IsEmpty => a.is_empty(),
-                ConditionOperator::IsNotEmpty => !a.is_empty(),
-                #[cfg(feature = "regex")]
-                ConditionOperator::Regex => regex::Regex::new(exp_str)
-                    .map(|re| re.is_match(a))
-                    .unwrap_or(false),
-                #[cfg(not(feature = "regex"))]
-                ConditionOperator::Regex => false,
-                _ => false,
-            };
-            return (passed, Some(a.to_string()), Some(exp_str.to_string()));
-        }
+    if let Some(exp_str) = expected.as_str()
+        && let Some(a) = extract_as_str(actual)
+    {
+        let passed = compare_strings(a, exp_str, operator, &options.strings).unwrap_or(false);
+        return (passed, Some(a.to_string()), Some(exp_str.to_string()));
     }
 
     // Boolean comparison
-    if let Some(exp_bool) = expected.as_bool() {
-        if let Some(act_bool) = actual.downcast_ref::<bool>() {
-            let passed = match operator {
-                ConditionOperator::Equals => *act_bool == exp_bool,
-                ConditionOperator::NotEquals => *act_bool != exp_bool,
-                _ => false,
+    if let Some(exp_bool) = expected.as_bool()
+        && let Some(act_bool) = extract_as_bool(actual)
+    {
+        let passed = match operator {
+            ConditionOperator::Equals => act_bool == exp_bool,
+            ConditionOperator::NotEquals => act_bool != exp_bool,
+            _ => false,
+        };
+        return (
+            passed,
+            Some(act_bool.to_string()),
+            Some(exp_bool.to_string()),
+        );
+    }
+
+    // Registered custom types
+    if let Some(result) = comparator::compare_custom_json(actual, expected, operator) {
+        return result;
+    }
+
+    (false, None, None)
+}
+
+/// Compare two values of the same type with an ordering operator.
+#[cfg(any(feature = "decimal", feature = "chrono"))]
+fn compare_parsed<T: PartialOrd + std::fmt::Display>(
+    actual: &T,
+    expected: &T,
+    operator: &ConditionOperator,
+) -> (bool, Option<String>, Option<String>) {
+    (
+        compare_numeric(actual, expected, operator),
+        Some(actual.to_string()),
+        Some(expected.to_string()),
+    )
+}
+
+/// Compare a `DateTime<Utc>`, `NaiveDate` or `Duration` field against a JSON
+/// operand. Returns `None` if the field isn't temporal or the operand can't be
+/// parsed as the field's type.
+#[cfg(feature = "chrono")]
+fn compare_temporal_to_json(
+    actual: &dyn Any,
+    expected: &serde_json::Value,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    if let Some(a) = actual.downcast_ref::<chrono::DateTime<chrono::Utc>>() {
+        return Some(compare_parsed(a, &temporal::json_to_datetime(expected)?, operator));
+    }
+    if let Some(a) = actual.downcast_ref::<chrono::NaiveDate>() {
+        return Some(compare_parsed(a, &temporal::json_to_date(expected)?, operator));
+    }
+    if let Some(a) = actual.downcast_ref::<chrono::Duration>() {
+        return Some(compare_parsed(a, &temporal::json_to_duration(expected)?, operator));
+    }
+    None
+}
+
+/// Compare a JSON document value against a `DateTime<Utc>`, `NaiveDate` or
+/// `Duration` operand. Returns `None` if the operand isn't temporal or the
+/// document value can't be parsed as its type.
+#[cfg(feature = "chrono")]
+pub(crate) fn compare_json_to_temporal(
+    actual: &serde_json::Value,
+    expected: &dyn Any,
+    operator: &ConditionOperator,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    if let Some(e) = expected.downcast_ref::<chrono::DateTime<chrono::Utc>>() {
+        return Some(compare_parsed(&temporal::json_to_datetime(actual)?, e, operator));
+    }
+    if let Some(e) = expected.downcast_ref::<chrono::NaiveDate>() {
+        return Some(compare_parsed(&temporal::json_to_date(actual)?, e, operator));
+    }
+    if let Some(e) = expected.downcast_ref::<chrono::Duration>() {
+        return Some(compare_parsed(&temporal::json_to_duration(actual)?, e, operator));
+    }
+    None
+}
+
+/// Split a `between` operand into its lower and upper bounds.
+///
+/// Accepts `{"min": .., "max": .., "inclusive": ..}`, where either bound may be
+/// omitted or `null` for an open range and `inclusive` is a bool or a
+/// `[min, max]` pair of bools (default `true`), or a `[min, max]` array
+/// with inclusive bounds.
+pub fn json_bounds(
+    expected: &serde_json::Value,
+) -> Option<(Bound<&serde_json::Value>, Bound<&serde_json::Value>)> {
+    let (min, max, inclusive) = match expected {
+        serde_json::Value::Array(items) if items.len() == 2 => {
+            (Some(&items[0]), Some(&items[1]), (true, true))
+        }
+        serde_json::Value::Object(map) => {
+            let inclusive = match map.get("inclusive") {
+                None => (true, true),
+                Some(serde_json::Value::Bool(b)) => (*b, *b),
+                Some(serde_json::Value::Array(flags)) => match flags.as_slice() {
+                    [serde_json::Value::Bool(lo), serde_json::Value::Bool(hi)] => (*lo, *hi),
+                    _ => return None,
+                },
+                Some(_) => return None,
             };
-            return (
-                passed,
-                Some(act_bool.to_string()),
-                Some(exp_bool.to_string()),
-            );
+            (map.get("min"), map.get("max"), inclusive)
         }
+        _ => return None,
+    };
+
+    Some((to_bound(min, inclusive.0), to_bound(max, inclusive.1)))
+}
+
+fn to_bound(value: Option<&serde_json::Value>, included: bool) -> Bound<&serde_json::Value> {
+    match value {
+        None | Some(serde_json::Value::Null) => Bound::Unbounded,
+        Some(v) if included => Bound::Included(v),
+        Some(v) => Bound::Excluded(v),
+    }
+}
+
+fn map_bound<'v, U>(
+    bound: Bound<&'v serde_json::Value>,
+    f: impl Fn(&'v serde_json::Value) -> Option<U>,
+) -> Option<Bound<U>> {
+    Some(match bound {
+        Bound::Included(v) => Bound::Included(f(v)?),
+        Bound::Excluded(v) => Bound::Excluded(f(v)?),
+        Bound::Unbounded => Bound::Unbounded,
+    })
+}
+
+/// Check whether a type-erased value lies within the bounds of a `between` operand.
+fn compare_json_range(
+    actual: &dyn Any,
+    expected: &serde_json::Value,
+    strings: &StringOptions,
+) -> (bool, Option<String>, Option<String>) {
+    let Some((min, max)) = json_bounds(expected) else {
+        return (false, None, None);
+    };
+
+    #[cfg(feature = "decimal")]
+    if let Some(result) = parsed_range(actual, min, max, json_to_decimal) {
+        return result;
+    }
+
+    #[cfg(feature = "chrono")]
+    if let Some(result) = parsed_range(actual, min, max, temporal::json_to_datetime)
+        .or_else(|| parsed_range(actual, min, max, temporal::json_to_date))
+        .or_else(|| parsed_range(actual, min, max, temporal::json_to_duration))
+    {
+        return result;
+    }
+
+    if let Some(a) = Numeric::from_any(actual)
+        && let (Some(lo), Some(hi)) = (map_bound(min, json_numeric), map_bound(max, json_numeric))
+    {
+        let bounds = (lo.as_ref(), hi.as_ref());
+        return (bounds.contains(&a), Some(a.to_string()), Some(format_bounds(bounds)));
+    }
+
+    if let Some(a) = extract_as_str(actual)
+        && let (Some(lo), Some(hi)) = (map_bound(min, |v| v.as_str()), map_bound(max, |v| v.as_str()))
+    {
+        let (passed, actual_str, expected_str) = string_range_contains(a, (lo, hi), strings);
+        return (passed, Some(actual_str), Some(expected_str));
     }
 
     (false, None, None)
 }
 
+fn json_numeric(value: &serde_json::Value) -> Option<Numeric> {
+    match value {
+        serde_json::Value::Number(n) => Some(Numeric::from_json(n)),
+        _ => None,
+    }
+}
+
+/// Range check for a field of type `T`, parsing each bound with `parse`.
+/// Returns `None` if the field isn't a `T` or a bound can't be parsed.
+#[cfg(any(feature = "decimal", feature = "chrono"))]
+fn parsed_range<T: PartialOrd + std::fmt::Display + 'static>(
+    actual: &dyn Any,
+    min: Bound<&serde_json::Value>,
+    max: Bound<&serde_json::Value>,
+    parse: impl Fn(&serde_json::Value) -> Option<T>,
+) -> Option<(bool, Option<String>, Option<String>)> {
+    let a = actual.downcast_ref::<T>()?;
+    let (lo, hi) = (map_bound(min, &parse)?, map_bound(max, &parse)?);
+    let bounds = (lo.as_ref(), hi.as_ref());
+    Some((bounds.contains(a), Some(a.to_string()), Some(format_bounds(bounds))))
+}
+
 fn combine_results(results: &[bool], mode: ConditionMode) -> bool {
     match mode {
         ConditionMode::AND => results.iter().all(|&r| r),
@@ -3,6 +3,7 @@
 //! This module contains specialized evaluators that handle the actual
 //! comparison logic for different condition selectors.
 
+mod collection;
 mod comparison;
 mod field;
 mod length;
@@ -12,7 +13,10 @@ mod value;
 
 #[cfg(feature = "json_condition")]
 mod json;
+#[cfg(feature = "json_condition")]
+mod prepared;
 
+pub use comparison::CompareOptions;
 pub use field::FieldEvaluator;
 pub use length::LengthEvaluator;
 pub use path::PathEvaluator;
@@ -21,4 +25,16 @@ pub use value::ValueEvaluator;
 
 #[cfg(feature = "json_condition")]
 pub use json::JsonEvaluator;
+#[cfg(feature = "json_condition")]
+pub(crate) use json::json_bounds;
+#[cfg(feature = "json_condition")]
+pub(crate) use comparison::{any_type_name, numeric_f64};
+#[cfg(feature = "json_condition")]
+pub(crate) use field::resolve_field;
+#[cfg(all(feature = "json_condition", feature = "decimal"))]
+pub(crate) use json::json_to_decimal;
+#[cfg(feature = "json_condition")]
+pub use prepared::PreparedCondition;
+#[cfg(feature = "regex")]
+pub use comparison::compile_regex;
 
@@ -3,21 +3,27 @@
 use std::any::Any;
 
 use crate::{
-    condition::ConditionOperator, error::MatchError, matchable::Matchable, result::ConditionResult,
+    condition::ConditionOperator,
+    error::MatchError, matchable::Matchable, result::ConditionResult,
 };
 
-use super::comparison::compare_any_values;
+#[cfg(feature = "json_condition")]
+use super::collection::evaluate_element_condition;
+use super::comparison::{compare_any_values, comparison_error, CompareOptions};
+use super::field::{none_result, presence_result};
 
 /// Evaluator for nested field path comparisons.
 pub struct PathEvaluator;
 
 impl PathEvaluator {
-    /// Evaluate a field path condition against a Matchable value.
+    /// Evaluate a field path condition against a Matchable value, comparing
+    /// with the condition's tolerance and string options.
     pub fn evaluate<T: Matchable>(
         value: &T,
         path: &[&str],
         expected: &dyn Any,
         operator: &ConditionOperator,
+        options: CompareOptions,
     ) -> ConditionResult {
         if path.is_empty() {
             return ConditionResult {
@@ -29,30 +35,27 @@ impl PathEvaluator {
             };
         }
 
-        // Try to use get_field_path first
-        if let Some(actual) = value.get_field_path(path) {
-            let (passed, actual_str, expected_str) = compare_any_values(actual, expected, operator);
-            return ConditionResult {
-                passed,
-                description: format!("field path '{:?}' {:?}", path, operator),
-                actual_value: actual_str,
-                expected_value: expected_str,
-                error: None,
-            };
+        #[cfg(feature = "json_condition")]
+        if operator.is_quantifier() {
+            return evaluate_element_condition(value, path, expected, operator);
+        }
+
+        if operator.is_presence() || operator.is_emptiness() {
+            return presence_result(value, path, operator, format!("field path '{:?}' {:?}", path, operator));
+        }
+
+        // Try to use get_field_path_value first
+        if let Some(actual) = value.get_field_path_value(path) {
+            return Self::compare(path, actual.as_any(), expected, operator, options);
         }
 
         // Fallback: try first field only (basic implementation)
-        match value.get_field(path[0]) {
+        match value.get_field_value(path[0]) {
             Some(actual) if path.len() == 1 => {
-                let (passed, actual_str, expected_str) =
-                    compare_any_values(actual, expected, operator);
-                ConditionResult {
-                    passed,
-                    description: format!("field path '{:?}' {:?}", path, operator),
-                    actual_value: actual_str,
-                    expected_value: expected_str,
-                    error: None,
-                }
+                Self::compare(path, actual.as_any(), expected, operator, options)
+            }
+            _ if value.field_is_none(path) == Some(true) => {
+                none_result(format!("field path '{:?}' {:?}", path, operator))
             }
             _ => ConditionResult {
                 passed: false,
@@ -66,5 +69,26 @@ impl PathEvaluator {
             },
         }
     }
-}
 
+    fn compare(
+        path: &[&str],
+        actual: &dyn Any,
+        expected: &dyn Any,
+        operator: &ConditionOperator,
+        options: CompareOptions,
+    ) -> ConditionResult {
+        let (passed, actual_str, expected_str) =
+            compare_any_values(actual, expected, operator, options);
+        // A comparison that applied always reports the actual value
+        let error = actual_str
+            .is_none()
+            .then(|| comparison_error(&path.join("."), actual, expected, operator));
+        ConditionResult {
+            passed,
+            description: format!("field path '{:?}' {:?}", path, operator),
+            actual_value: actual_str,
+            expected_value: expected_str,
+            error,
+        }
+    }
+}
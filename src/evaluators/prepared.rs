@@ -0,0 +1,449 @@
+//! Operand data derived from JSON conditions ahead of evaluation.
+//!
+//! A [`PreparedCondition`] mirrors the shape of a [`JsonNestedCondition`] and
+//! holds per-rule data that is expensive to rebuild on every evaluation, such
+//! as the hash set behind an `in` operand or a compiled regex.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt;
+
+#[cfg(feature = "chrono")]
+use crate::temporal;
+use crate::{
+    condition::{ConditionOperator, JsonCondition, JsonNestedCondition, StringOptions, Tolerance},
+    error::MatchError,
+    expression::Expression,
+};
+
+#[cfg(feature = "regex")]
+use super::comparison::{compile_regex, regex_matches};
+use super::json::{extract_as_bool, extract_as_str};
+
+/// Prepared data for a condition group, in the same order as its rules and nested groups.
+#[derive(Debug, Clone, Default)]
+pub struct PreparedCondition {
+    pub(crate) rules: Vec<PreparedRule>,
+    pub(crate) nested: Vec<PreparedCondition>,
+}
+
+impl PreparedCondition {
+    /// Prepare every rule of a condition group, recursively.
+    pub fn new(condition: &JsonNestedCondition) -> Self {
+        Self {
+            rules: condition.rules.iter().map(PreparedRule::new).collect(),
+            nested: condition.nested.iter().map(|n| Self::new(n)).collect(),
+        }
+    }
+
+    /// Use `tolerance` for float equality in every rule of the group that
+    /// doesn't set its own, recursively.
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.set_tolerance(tolerance);
+        self
+    }
+
+    fn set_tolerance(&mut self, tolerance: Tolerance) {
+        for rule in &mut self.rules {
+            rule.default_tolerance = tolerance;
+            if let Some(Ok(elements)) = &mut rule.elements {
+                elements.1.set_tolerance(tolerance);
+            }
+        }
+        for nested in &mut self.nested {
+            nested.set_tolerance(tolerance);
+        }
+    }
+
+    /// The first error produced while preparing the group, searching depth-first.
+    pub fn first_error(&self) -> Option<&MatchError> {
+        self.rules
+            .iter()
+            .find_map(PreparedRule::error)
+            .or_else(|| self.nested.iter().find_map(Self::first_error))
+    }
+}
+
+/// Prepared data for a single rule.
+#[derive(Debug, Clone, Default)]
+pub struct PreparedRule {
+    /// Hashed members of an `in`/`not_in` operand
+    pub(crate) set: Option<ValueSet>,
+    /// Compiled pattern of a `regex` operand
+    #[cfg(feature = "regex")]
+    pub(crate) regex: Option<Result<regex::Regex, MatchError>>,
+    /// Allowed difference for float equality set on the rule itself
+    pub(crate) tolerance: Option<Tolerance>,
+    /// Allowed difference for float equality when the rule sets none
+    pub(crate) default_tolerance: Tolerance,
+    /// Per-element condition group of an `any_element`/`all_elements` operand
+    pub(crate) elements: Option<Result<Box<(JsonNestedCondition, PreparedCondition)>, MatchError>>,
+    /// Arithmetic expression operand
+    pub(crate) expression: Option<Result<Expression, MatchError>>,
+    /// How strings are prepared before matching the compiled pattern
+    #[cfg(feature = "regex")]
+    pub(crate) strings: StringOptions,
+}
+
+impl PreparedRule {
+    fn new(rule: &JsonCondition) -> Self {
+        let strings = rule.string_options();
+        let set = match rule.operator {
+            ConditionOperator::In | ConditionOperator::NotIn => ValueSet::from_json(&rule.value, strings),
+            _ => None,
+        };
+
+        let elements = rule
+            .operator
+            .is_quantifier()
+            .then(|| Self::parse_elements(&rule.value));
+
+        #[cfg(feature = "regex")]
+        let regex = match (&rule.operator, rule.value.as_str()) {
+            (ConditionOperator::Regex, Some(pattern)) => {
+                Some(compile_regex(pattern, strings.case_insensitive))
+            }
+            _ => None,
+        };
+
+        Self {
+            set,
+            #[cfg(feature = "regex")]
+            regex,
+            tolerance: rule.float_tolerance(),
+            default_tolerance: Tolerance::default(),
+            elements,
+            expression: rule.value_expression(),
+            #[cfg(feature = "regex")]
+            strings,
+        }
+    }
+
+    /// The operand parsed as an arithmetic expression, if it is one.
+    pub fn expression(&self) -> Option<Result<&Expression, &MatchError>> {
+        self.expression.as_ref().map(Result::as_ref)
+    }
+
+    /// Parse and prepare the condition group of an `any_element`/`all_elements` operand.
+    pub(crate) fn parse_elements(
+        value: &serde_json::Value,
+    ) -> Result<Box<(JsonNestedCondition, PreparedCondition)>, MatchError> {
+        let condition: JsonNestedCondition =
+            serde_json::from_value(value.clone()).map_err(|e| MatchError::InvalidOperand {
+                operator: "element quantifier".to_string(),
+                message: e.to_string(),
+            })?;
+        let prepared = PreparedCondition::new(&condition);
+        Ok(Box::new((condition, prepared)))
+    }
+
+    /// The per-element condition group of an `any_element`/`all_elements`
+    /// rule, if it was parsed successfully.
+    pub fn elements(&self) -> Option<(&JsonNestedCondition, &PreparedCondition)> {
+        match &self.elements {
+            Some(Ok(elements)) => Some((&elements.0, &elements.1)),
+            _ => None,
+        }
+    }
+
+    /// The tolerance for float equality in this rule.
+    pub fn tolerance(&self) -> Tolerance {
+        self.tolerance.unwrap_or(self.default_tolerance)
+    }
+
+    /// The error produced while preparing this rule, if any.
+    pub fn error(&self) -> Option<&MatchError> {
+        #[cfg(feature = "regex")]
+        if let Some(Err(err)) = &self.regex {
+            return Some(err);
+        }
+        if let Some(Err(err)) = &self.expression {
+            return Some(err);
+        }
+        match &self.elements {
+            Some(Err(err)) => Some(err),
+            Some(Ok(elements)) => elements.1.first_error(),
+            None => None,
+        }
+    }
+
+    /// Evaluate the rule against a field value using prepared operand data.
+    ///
+    /// Returns `None` when nothing was prepared for this rule's operator.
+    pub fn compare(
+        &self,
+        actual: &dyn Any,
+        operator: &ConditionOperator,
+    ) -> Option<(bool, Option<String>, Option<String>)> {
+        if let Some(set) = &self.set {
+            return Some(set.compare(actual, operator));
+        }
+
+        #[cfg(feature = "regex")]
+        if let Some(Ok(re)) = &self.regex {
+            let pattern = Some(re.as_str().to_string());
+            return Some(match extract_as_str(actual) {
+                Some(a) => (regex_matches(re, a, &self.strings), Some(a.to_string()), pattern),
+                None => (false, None, pattern),
+            });
+        }
+
+        None
+    }
+}
+
+/// Hashable representation of a scalar value.
+///
+/// Integral numbers are stored as integers regardless of their source type,
+/// so `3`, `3u8` and `3.0` all produce the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SetKey {
+    Null,
+    Bool(bool),
+    Int(i128),
+    Float(u64),
+    Str(String),
+}
+
+impl SetKey {
+    fn from_f64(v: f64) -> Option<Self> {
+        if v.is_nan() {
+            return None;
+        }
+        if v.fract() == 0.0 && v.abs() < 1e38 {
+            return Some(SetKey::Int(v as i128));
+        }
+        Some(SetKey::Float(v.to_bits()))
+    }
+
+    fn from_json(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Null => Some(SetKey::Null),
+            serde_json::Value::Bool(b) => Some(SetKey::Bool(*b)),
+            serde_json::Value::String(s) => Some(SetKey::Str(s.clone())),
+            serde_json::Value::Number(n) => {
+                if let Some(i) = n.as_i64() {
+                    Some(SetKey::Int(i as i128))
+                } else if let Some(u) = n.as_u64() {
+                    Some(SetKey::Int(u as i128))
+                } else {
+                    n.as_f64().and_then(Self::from_f64)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Prepare a string key with `strings`; other keys are unchanged.
+    fn prepare(self, strings: &StringOptions) -> Self {
+        match self {
+            SetKey::Str(s) if *strings != StringOptions::default() => {
+                SetKey::Str(strings.apply(&s).into_owned())
+            }
+            key => key,
+        }
+    }
+
+    /// Build a key from a type-erased field value.
+    pub fn from_any(actual: &dyn Any) -> Option<Self> {
+        if let Some(v) = actual.downcast_ref::<serde_json::Value>() {
+            return Self::from_json(v);
+        }
+        if let Some(s) = extract_as_str(actual) {
+            return Some(SetKey::Str(s.to_string()));
+        }
+        if let Some(b) = extract_as_bool(actual) {
+            return Some(SetKey::Bool(b));
+        }
+
+        macro_rules! int_key {
+            ($($t:ty),*) => {
+                $(
+                    if let Some(v) = actual.downcast_ref::<$t>() {
+                        return Some(SetKey::Int(*v as i128));
+                    }
+                )*
+            };
+        }
+        int_key!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+        if let Some(v) = actual.downcast_ref::<u128>() {
+            return match i128::try_from(*v) {
+                Ok(i) => Some(SetKey::Int(i)),
+                Err(_) => Self::from_f64(*v as f64),
+            };
+        }
+        if let Some(v) = actual.downcast_ref::<f64>() {
+            return Self::from_f64(*v);
+        }
+        if let Some(v) = actual.downcast_ref::<f32>() {
+            return Self::from_f64(*v as f64);
+        }
+        None
+    }
+}
+
+#[cfg(feature = "decimal")]
+impl SetKey {
+    /// The exact decimal value of a numeric or numeric string key.
+    fn to_decimal(&self) -> Option<rust_decimal::Decimal> {
+        use std::str::FromStr;
+
+        match self {
+            SetKey::Int(i) => rust_decimal::Decimal::try_from_i128_with_scale(*i, 0).ok(),
+            SetKey::Float(bits) => {
+                rust_decimal::Decimal::from_str(&f64::from_bits(*bits).to_string()).ok()
+            }
+            SetKey::Str(s) => rust_decimal::Decimal::from_str(s.trim()).ok(),
+            SetKey::Null | SetKey::Bool(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl SetKey {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            SetKey::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// A duration string, or a number of seconds.
+    fn to_duration(&self) -> Option<chrono::Duration> {
+        match self {
+            SetKey::Str(s) => temporal::parse_duration(s),
+            SetKey::Int(i) => chrono::Duration::try_seconds(i64::try_from(*i).ok()?),
+            SetKey::Float(bits) => {
+                chrono::Duration::try_milliseconds((f64::from_bits(*bits) * 1000.0).round() as i64)
+            }
+            SetKey::Null | SetKey::Bool(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for SetKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetKey::Null => write!(f, "null"),
+            SetKey::Bool(b) => write!(f, "{}", b),
+            SetKey::Int(i) => write!(f, "{}", i),
+            SetKey::Float(bits) => write!(f, "{}", f64::from_bits(*bits)),
+            SetKey::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Hashed membership set built from a JSON array operand.
+///
+/// Members are also parsed up front as decimals, timestamps, dates and
+/// durations, so fields of those types are looked up without re-parsing.
+#[derive(Debug, Clone, Default)]
+pub struct ValueSet {
+    members: HashSet<SetKey>,
+    display: String,
+    /// How string members and field values are prepared before hashing
+    strings: StringOptions,
+    #[cfg(feature = "decimal")]
+    decimals: HashSet<rust_decimal::Decimal>,
+    #[cfg(feature = "chrono")]
+    datetimes: HashSet<chrono::DateTime<chrono::Utc>>,
+    #[cfg(feature = "chrono")]
+    dates: HashSet<chrono::NaiveDate>,
+    #[cfg(feature = "chrono")]
+    durations: HashSet<chrono::Duration>,
+    /// Members relative to the current time, such as `"now-7d"`, which are
+    /// resolved on each lookup
+    #[cfg(feature = "chrono")]
+    relative: Vec<String>,
+}
+
+impl ValueSet {
+    /// Build a set from a JSON array, preparing string members with `strings`.
+    /// Returns `None` if `value` is not an array.
+    ///
+    /// Nested arrays and objects can't be compared against a field and are skipped.
+    pub fn from_json(value: &serde_json::Value, strings: StringOptions) -> Option<Self> {
+        let keys: Vec<SetKey> = value.as_array()?.iter().filter_map(SetKey::from_json).collect();
+        let mut set = Self {
+            display: value.to_string(),
+            strings,
+            ..Self::default()
+        };
+
+        #[cfg(feature = "decimal")]
+        {
+            set.decimals = keys.iter().filter_map(SetKey::to_decimal).collect();
+        }
+        #[cfg(feature = "chrono")]
+        for key in &keys {
+            match key.as_str() {
+                Some(text) if temporal::is_relative(text) => set.relative.push(text.to_string()),
+                Some(text) => {
+                    set.datetimes.extend(temporal::parse_datetime(text));
+                    set.dates.extend(temporal::parse_date(text));
+                }
+                None => {}
+            }
+            set.durations.extend(key.to_duration());
+        }
+
+        set.members = keys.into_iter().map(|key| key.prepare(&strings)).collect();
+        Some(set)
+    }
+
+    /// Evaluate an `in`/`not_in` operator against a field value.
+    /// Returns (passed, actual_string, expected_string).
+    pub fn compare(
+        &self,
+        actual: &dyn Any,
+        operator: &ConditionOperator,
+    ) -> (bool, Option<String>, Option<String>) {
+        let (found, actual_str) = match self.find(actual) {
+            Some(result) => result,
+            None => return (false, None, Some(self.display.clone())),
+        };
+        let passed = match operator {
+            ConditionOperator::In => found,
+            ConditionOperator::NotIn => !found,
+            _ => false,
+        };
+        (passed, Some(actual_str), Some(self.display.clone()))
+    }
+
+    /// Whether the set contains a field value, and the value's display string.
+    fn find(&self, actual: &dyn Any) -> Option<(bool, String)> {
+        // Decimals aren't hashed as floats; they're looked up exactly
+        #[cfg(feature = "decimal")]
+        if let Some(a) = actual.downcast_ref::<rust_decimal::Decimal>() {
+            return Some((self.decimals.contains(a), a.to_string()));
+        }
+
+        #[cfg(feature = "chrono")]
+        {
+            if let Some(a) = actual.downcast_ref::<chrono::DateTime<chrono::Utc>>() {
+                let found = self.datetimes.contains(a)
+                    || self.find_relative(|text| temporal::parse_datetime(text).as_ref() == Some(a));
+                return Some((found, a.to_string()));
+            }
+            if let Some(a) = actual.downcast_ref::<chrono::NaiveDate>() {
+                let found = self.dates.contains(a)
+                    || self.find_relative(|text| temporal::parse_date(text).as_ref() == Some(a));
+                return Some((found, a.to_string()));
+            }
+            if let Some(a) = actual.downcast_ref::<chrono::Duration>() {
+                return Some((self.durations.contains(a), a.to_string()));
+            }
+        }
+
+        let key = SetKey::from_any(actual)?;
+        let display = key.to_string();
+        Some((self.members.contains(&key.prepare(&self.strings)), display))
+    }
+
+    /// Whether a member relative to the current time matches once resolved.
+    #[cfg(feature = "chrono")]
+    fn find_relative(&self, matches: impl Fn(&str) -> bool) -> bool {
+        self.relative.iter().any(|text| matches(text))
+    }
+}
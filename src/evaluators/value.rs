@@ -15,6 +15,10 @@ impl ValueEvaluator {
         let passed = match operator {
             ConditionOperator::Equals => value == expected,
             ConditionOperator::NotEquals => value != expected,
+            ConditionOperator::IsNone => value.is_none(),
+            ConditionOperator::IsSome => !value.is_none(),
+            ConditionOperator::IsEmpty => value.is_empty() == Some(true),
+            ConditionOperator::IsNotEmpty => value.is_empty() == Some(false),
             _ => false,
         };
 
@@ -0,0 +1,296 @@
+//! Arithmetic expressions over the fields of a value.
+//!
+//! Expressions let a JSON rule compare computed quantities, such as
+//! `price_change_24h / price > 0.05` or `volume > avg_volume * 3`. They are
+//! written as single-key objects naming the operation:
+//!
+//! ```json
+//! {"field": {"$div": [{"$field": "price_change_24h"}, {"$field": "price"}]}, "operator": "greater_than", "value": 0.05}
+//! {"field": "volume", "operator": "greater_than", "value": {"$mul": [{"$field": "avg_volume"}, 3]}}
+//! ```
+//!
+//! Expressions are evaluated as `f64`. A missing or non-numeric field and a
+//! division by zero are reported as errors. `$add`, `$mul`, `$min` and `$max`
+//! need at least one operand.
+
+use std::any::Any;
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, de::Error as _};
+
+use crate::{error::MatchError, evaluators::resolve_field, matchable::Matchable};
+
+/// The keys naming the operation of an expression object.
+const OPERATORS: [&str; 8] = ["$field", "$add", "$sub", "$mul", "$div", "$abs", "$min", "$max"];
+
+/// A numeric expression over the fields of a value.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum Expression {
+    /// The value of a field, by name or dotted path: `{"$field": "price"}`
+    #[serde(rename = "$field")]
+    Field(String),
+    /// The sum of the operands: `{"$add": [a, b, ...]}`
+    #[serde(rename = "$add", deserialize_with = "non_empty")]
+    Add(Vec<Expression>),
+    /// The first operand minus the second: `{"$sub": [a, b]}`
+    #[serde(rename = "$sub")]
+    Sub(Box<Expression>, Box<Expression>),
+    /// The product of the operands: `{"$mul": [a, b, ...]}`
+    #[serde(rename = "$mul", deserialize_with = "non_empty")]
+    Mul(Vec<Expression>),
+    /// The first operand divided by the second: `{"$div": [a, b]}`
+    #[serde(rename = "$div")]
+    Div(Box<Expression>, Box<Expression>),
+    /// The absolute value of the operand: `{"$abs": a}`
+    #[serde(rename = "$abs")]
+    Abs(Box<Expression>),
+    /// The smallest operand: `{"$min": [a, b, ...]}`
+    #[serde(rename = "$min", deserialize_with = "non_empty")]
+    Min(Vec<Expression>),
+    /// The largest operand: `{"$max": [a, b, ...]}`
+    #[serde(rename = "$max", deserialize_with = "non_empty")]
+    Max(Vec<Expression>),
+    /// A number
+    #[serde(untagged)]
+    Literal(f64),
+}
+
+impl Expression {
+    /// Reference a field by name or dotted path.
+    pub fn field(name: impl Into<String>) -> Self {
+        Expression::Field(name.into())
+    }
+
+    /// Parse a rule operand as an expression.
+    ///
+    /// Returns `None` unless the operand is an object with a single
+    /// expression key, and an error if such an object isn't a valid
+    /// expression, e.g. `{"$add": []}`.
+    pub(crate) fn from_operand(
+        operand: &serde_json::Value,
+    ) -> Option<Result<Expression, MatchError>> {
+        let object = operand.as_object()?;
+        let (key, _) = object.iter().next().filter(|_| object.len() == 1)?;
+        if !OPERATORS.contains(&key.as_str()) {
+            return None;
+        }
+        Some(
+            serde_json::from_value(operand.clone()).map_err(|e| MatchError::InvalidOperand {
+                operator: key.clone(),
+                message: e.to_string(),
+            }),
+        )
+    }
+
+    /// Evaluate the expression against a value.
+    pub fn evaluate<T: Matchable>(&self, value: &T) -> Result<f64, MatchError> {
+        Ok(match self {
+            Expression::Field(name) => {
+                let field =
+                    resolve_field(value, name).ok_or_else(|| MatchError::FieldNotFound {
+                        field: name.clone(),
+                        type_name: value.type_name().to_string(),
+                    })?;
+                let field = field.as_any();
+                number(field).ok_or_else(|| MatchError::TypeMismatch {
+                    field: name.clone(),
+                    expected: "number".to_string(),
+                    actual: crate::evaluators::any_type_name(field).to_string(),
+                })?
+            }
+            Expression::Add(operands) => Self::fold("$add", operands, value, |a, b| a + b)?,
+            Expression::Sub(a, b) => a.evaluate(value)? - b.evaluate(value)?,
+            Expression::Mul(operands) => Self::fold("$mul", operands, value, |a, b| a * b)?,
+            Expression::Div(a, b) => {
+                let divisor = b.evaluate(value)?;
+                if divisor == 0.0 {
+                    return Err(MatchError::DivisionByZero {
+                        expression: self.to_string(),
+                    });
+                }
+                a.evaluate(value)? / divisor
+            }
+            Expression::Abs(a) => a.evaluate(value)?.abs(),
+            Expression::Min(operands) => Self::fold("$min", operands, value, f64::min)?,
+            Expression::Max(operands) => Self::fold("$max", operands, value, f64::max)?,
+            Expression::Literal(n) => *n,
+        })
+    }
+
+    /// The names of all fields the expression refers to.
+    pub fn fields(&self) -> Vec<&str> {
+        let mut fields = Vec::new();
+        self.collect_fields(&mut fields);
+        fields
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut Vec<&'a str>) {
+        match self {
+            Expression::Field(name) => fields.push(name),
+            Expression::Add(operands)
+            | Expression::Mul(operands)
+            | Expression::Min(operands)
+            | Expression::Max(operands) => operands.iter().for_each(|e| e.collect_fields(fields)),
+            Expression::Sub(a, b) | Expression::Div(a, b) => {
+                a.collect_fields(fields);
+                b.collect_fields(fields);
+            }
+            Expression::Abs(a) => a.collect_fields(fields),
+            Expression::Literal(_) => {}
+        }
+    }
+
+    fn fold<T: Matchable>(
+        operator: &str,
+        operands: &[Expression],
+        value: &T,
+        op: fn(f64, f64) -> f64,
+    ) -> Result<f64, MatchError> {
+        let Some((first, rest)) = operands.split_first() else {
+            return Err(MatchError::InvalidOperand {
+                operator: operator.to_string(),
+                message: "expected at least one operand".to_string(),
+            });
+        };
+        rest.iter()
+            .try_fold(first.evaluate(value)?, |acc, e| Ok(op(acc, e.evaluate(value)?)))
+    }
+
+    fn is_binary(&self) -> bool {
+        matches!(
+            self,
+            Expression::Add(_) | Expression::Sub(..) | Expression::Mul(_) | Expression::Div(..)
+        )
+    }
+}
+
+impl From<f64> for Expression {
+    fn from(n: f64) -> Self {
+        Expression::Literal(n)
+    }
+}
+
+/// Deserialize the operand list of `$add`, `$mul`, `$min` or `$max`, which
+/// has no meaningful value when empty.
+fn non_empty<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Expression>, D::Error> {
+    let operands = Vec::<Expression>::deserialize(deserializer)?;
+    if operands.is_empty() {
+        return Err(D::Error::invalid_length(0, &"at least one operand"));
+    }
+    Ok(operands)
+}
+
+/// Widen a numeric field value to `f64`.
+fn number(value: &dyn Any) -> Option<f64> {
+    #[cfg(feature = "decimal")]
+    if let Some(d) = value.downcast_ref::<rust_decimal::Decimal>() {
+        use rust_decimal::prelude::ToPrimitive;
+        return d.to_f64();
+    }
+    crate::evaluators::numeric_f64(value)
+}
+
+/// Infix form, e.g. `abs(price - open) / open`.
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operand = |f: &mut fmt::Formatter<'_>, e: &Expression| {
+            if e.is_binary() {
+                write!(f, "({})", e)
+            } else {
+                write!(f, "{}", e)
+            }
+        };
+        let infix = |f: &mut fmt::Formatter<'_>, operands: &[&Expression], symbol: &str| {
+            for (i, e) in operands.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {} ", symbol)?;
+                }
+                operand(f, e)?;
+            }
+            Ok(())
+        };
+        let call = |f: &mut fmt::Formatter<'_>, name: &str, operands: &[Expression]| {
+            write!(f, "{}(", name)?;
+            for (i, e) in operands.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", e)?;
+            }
+            f.write_str(")")
+        };
+
+        match self {
+            Expression::Field(name) if crate::dsl::is_plain_path(name) => f.write_str(name),
+            Expression::Field(name) => write!(f, "`{}`", name),
+            Expression::Add(operands) => infix(f, &operands.iter().collect::<Vec<_>>(), "+"),
+            Expression::Sub(a, b) => infix(f, &[a, b], "-"),
+            Expression::Mul(operands) => infix(f, &operands.iter().collect::<Vec<_>>(), "*"),
+            Expression::Div(a, b) => infix(f, &[a, b], "/"),
+            Expression::Abs(a) => call(f, "abs", std::slice::from_ref(a)),
+            Expression::Min(operands) => call(f, "min", operands),
+            Expression::Max(operands) => call(f, "max", operands),
+            Expression::Literal(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize() {
+        let expression: Expression =
+            serde_json::from_value(json!({"$abs": {"$sub": [{"$field": "open"}, {"$field": "price"}]}})).unwrap();
+        assert_eq!(
+            expression,
+            Expression::Abs(Box::new(Expression::Sub(
+                Box::new(Expression::field("open")),
+                Box::new(Expression::field("price")),
+            )))
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let expression = Expression::Div(
+            Box::new(Expression::field("price_change_24h")),
+            Box::new(Expression::field("price")),
+        );
+        assert_eq!(
+            serde_json::to_value(&expression).unwrap(),
+            json!({"$div": [{"$field": "price_change_24h"}, {"$field": "price"}]})
+        );
+    }
+
+    #[test]
+    fn test_empty_operands() {
+        assert!(serde_json::from_value::<Expression>(json!({"$min": []})).is_err());
+        assert!(matches!(
+            Expression::from_operand(&json!({"$max": []})),
+            Some(Err(MatchError::InvalidOperand { operator, .. })) if operator == "$max"
+        ));
+        assert!(Expression::Add(Vec::new()).evaluate(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_single_operand() {
+        let quote = json!({"price": 100.0});
+        assert_eq!(Expression::Mul(vec![Expression::field("price")]).evaluate(&quote), Ok(100.0));
+    }
+
+    #[test]
+    fn test_division_by_zero() {
+        let quote = json!({"price": 100.0, "open": 96.0});
+        let expression: Expression =
+            serde_json::from_value(json!({"$div": [{"$field": "price"}, {"$sub": [{"$field": "open"}, 96]}]})).unwrap();
+        assert_eq!(
+            expression.evaluate(&quote),
+            Err(MatchError::DivisionByZero {
+                expression: "price / (open - 96)".to_string()
+            })
+        );
+    }
+}
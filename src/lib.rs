@@ -11,7 +11,7 @@
 //! - Numeric comparisons on fields
 //! - Detailed match results with error information
 //! - Builder pattern for ergonomic API
-//! - Optional serde, regex, and parallel processing support
+//! - Optional serde, regex, decimal, date/time and parallel processing support
 //!
 //! ## Quick Start
 //!
@@ -52,6 +52,17 @@
 //! assert!(matcher.matches(&"good"));
 //! ```
 //!
+//! ## Expression Language
+//!
+//! With the `json_condition` feature, conditions can also be written as text:
+//!
+//! ```rust,ignore
+//! use condition_matcher::{JsonMatcher, Matcher};
+//!
+//! let matcher = JsonMatcher::from_dsl(r#"price >= 100 && (symbol starts_with "BTC" || volume > 1e6)"#)?;
+//! assert!(matcher.matches(&quote));
+//! ```
+//!
 //! ## Batch Operations
 //!
 //! ```rust,ignore
@@ -67,14 +78,27 @@
 //! let all_matches = batch::parallel::evaluate_matrix(&records, &matchers);
 //! ```
 
+// Lets the derive macro refer to `::condition_matcher` from inside this crate
+extern crate self as condition_matcher;
+
 // Core modules
 mod traits;
 mod condition;
 mod matchable;
+mod dynamic;
 mod matchers;
 mod evaluators;
 mod result;
 mod error;
+mod schema;
+mod comparator;
+#[cfg(feature = "chrono")]
+mod temporal;
+
+#[cfg(feature = "json_condition")]
+mod dsl;
+#[cfg(feature = "json_condition")]
+mod expression;
 
 /// Builder module for creating matchers.
 pub mod builder;
@@ -108,11 +132,18 @@ pub use matchers::JsonMatcher;
 // ============================================================================
 
 pub use condition::{
-    Condition, ConditionMode, ConditionOperator, ConditionSelector, NestedCondition,
+    Condition, ConditionMode, ConditionOperator, ConditionSelector, NestedCondition, StringOptions,
+    Tolerance,
 };
 
+#[cfg(feature = "unicode")]
+pub use condition::Normalization;
+
+#[cfg(feature = "json_condition")]
+pub use condition::{JsonCondition, JsonField, JsonNestedCondition};
+
 #[cfg(feature = "json_condition")]
-pub use condition::{JsonCondition, JsonNestedCondition};
+pub use expression::Expression;
 
 // ============================================================================
 // Builder
@@ -131,11 +162,41 @@ pub use result::{JsonConditionResult, JsonEvalResult};
 
 pub use error::MatchError;
 
+#[cfg(feature = "json_condition")]
+pub use error::{ParseError, ValidationError};
+
+// ============================================================================
+// Custom Types
+// ============================================================================
+
+pub use comparator::{register_comparator, unregister_comparator, Comparator};
+
+// ============================================================================
+// Date and Time
+// ============================================================================
+
+#[cfg(feature = "chrono")]
+pub use temporal::{now, set_clock, Clock, ClockGuard, FixedClock, SystemClock};
+
+// ============================================================================
+// Schemas
+// ============================================================================
+
+pub use schema::{FieldInfo, FieldKind, FieldSchema, Schema};
+
+// ============================================================================
+// Expression Language
+// ============================================================================
+
+#[cfg(feature = "json_condition")]
+pub use dsl::parse_condition;
+
 // ============================================================================
 // Data Access
 // ============================================================================
 
-pub use matchable::Matchable;
+pub use dynamic::DynMatchable;
+pub use matchable::{FieldValue, Matchable};
 
 // ============================================================================
 // Derive Macro
@@ -1,6 +1,9 @@
 use std::any::Any;
 use std::collections::HashMap;
 
+use crate::dynamic;
+use crate::schema::FieldInfo;
+
 /// Trait for types that can be matched against conditions.
 /// 
 /// This trait allows different types to opt-in to specific matching capabilities.
@@ -32,12 +35,72 @@ pub trait Matchable: PartialEq + Sized {
         None
     }
     
+    /// Get a field value that may be computed on access.
+    ///
+    /// Unlike [`get_field`](Self::get_field), this can return an owned value,
+    /// so fields that don't exist in memory (virtual fields, converted values)
+    /// can still be matched. The default borrows from `get_field`; evaluators
+    /// use this method for single-field lookups.
+    fn get_field_value(&self, field: &str) -> Option<FieldValue<'_>> {
+        self.get_field(field).map(FieldValue::Borrowed)
+    }
+
     /// Get a nested field value by path.
     /// Default implementation walks through get_field calls.
     fn get_field_path(&self, _path: &[&str]) -> Option<&dyn Any> {
         None
     }
+
+    /// Get a nested field value by path, possibly computed on access.
+    ///
+    /// The owned counterpart of [`get_field_path`](Self::get_field_path), used
+    /// by the evaluators. The default resolves single-segment paths through
+    /// [`get_field_value`](Self::get_field_value) and borrows from
+    /// `get_field_path` otherwise.
+    fn get_field_path_value(&self, path: &[&str]) -> Option<FieldValue<'_>> {
+        match path {
+            [field] => self.get_field_value(field),
+            _ => self.get_field_path(path).map(FieldValue::Borrowed),
+        }
+    }
     
+    /// Get the elements of a collection field by path, for the `AnyElement`
+    /// and `AllElements` operators.
+    ///
+    /// Generated by `#[derive(Matchable)]` for `#[matchable(nested)]` fields
+    /// holding a `Vec` or `VecDeque`. Returns None if the path doesn't lead to
+    /// a collection of matchable elements.
+    fn get_field_elements(&self, _path: &[&str]) -> Option<Vec<&dyn dynamic::DynMatchable>> {
+        None
+    }
+
+    /// Check whether the field at `path` holds no value, such as a `None`
+    /// option or a JSON `null`, for the `IsNone` and `IsSome` operators.
+    /// Comparisons against such a field don't match, without an error.
+    ///
+    /// Returns None if the path doesn't name a field at all, which is what
+    /// `Exists` and `NotExists` test. The default only knows the fields
+    /// resolved by [`get_field_path_value`](Self::get_field_path_value), so a
+    /// `None` option that `get_field` can't return looks missing.
+    /// `#[derive(Matchable)]` generates this method; manual implementations
+    /// with optional fields must override it to return `Some(true)` for them.
+    fn field_is_none(&self, path: &[&str]) -> Option<bool> {
+        self.get_field_path_value(path).map(|v| v.is_null())
+    }
+
+    /// Check whether the string, collection or map at `path` is empty, for
+    /// the `IsEmpty` and `IsNotEmpty` operators. A `None` field is empty.
+    ///
+    /// Returns None when this can't be decided from the field's type; the
+    /// evaluators then fall back to [`field_is_none`](Self::field_is_none),
+    /// the runtime type of the field value and
+    /// [`get_field_elements`](Self::get_field_elements).
+    /// `#[derive(Matchable)]` generates this method for fields of any type
+    /// with an `is_empty` method from the standard library.
+    fn field_is_empty(&self, _path: &[&str]) -> Option<bool> {
+        None
+    }
+
     /// Get the type name as a string
     fn type_name(&self) -> &str {
         std::any::type_name::<Self>()
@@ -52,6 +115,48 @@ pub trait Matchable: PartialEq + Sized {
     fn is_none(&self) -> bool {
         false
     }
+
+    /// Static metadata for the fields reachable through `get_field`.
+    /// Generated by `#[derive(Matchable)]`; empty for types without named fields.
+    fn field_info() -> &'static [FieldInfo] {
+        &[]
+    }
+}
+
+/// A field value returned by [`Matchable::get_field_value`].
+pub enum FieldValue<'a> {
+    /// A reference to data stored in the matched value
+    Borrowed(&'a dyn Any),
+    /// A value computed on access
+    Owned(Box<dyn Any>),
+}
+
+impl FieldValue<'_> {
+    /// Get the value as a type-erased reference.
+    pub fn as_any(&self) -> &dyn Any {
+        match self {
+            FieldValue::Borrowed(v) => *v,
+            FieldValue::Owned(v) => v.as_ref(),
+        }
+    }
+
+    /// Whether the value is a JSON `null`.
+    pub fn is_null(&self) -> bool {
+        #[cfg(feature = "json_condition")]
+        if let Some(json) = self.as_any().downcast_ref::<serde_json::Value>() {
+            return json.is_null();
+        }
+        false
+    }
+}
+
+impl std::fmt::Debug for FieldValue<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::Borrowed(_) => write!(f, "FieldValue::Borrowed(..)"),
+            FieldValue::Owned(_) => write!(f, "FieldValue::Owned(..)"),
+        }
+    }
 }
 
 // ============================================================================
@@ -115,6 +220,30 @@ impl<T: Matchable + 'static> Matchable for Option<T> {
         self.as_ref().and_then(|v| v.get_field(field))
     }
 
+    fn get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
+        self.as_ref().and_then(|v| v.get_field_path(path))
+    }
+
+    fn get_field_value(&self, field: &str) -> Option<FieldValue<'_>> {
+        self.as_ref().and_then(|v| v.get_field_value(field))
+    }
+
+    fn get_field_path_value(&self, path: &[&str]) -> Option<FieldValue<'_>> {
+        self.as_ref().and_then(|v| v.get_field_path_value(path))
+    }
+
+    fn get_field_elements(&self, path: &[&str]) -> Option<Vec<&dyn dynamic::DynMatchable>> {
+        self.as_ref().and_then(|v| v.get_field_elements(path))
+    }
+
+    fn field_is_none(&self, path: &[&str]) -> Option<bool> {
+        self.as_ref().and_then(|v| v.field_is_none(path))
+    }
+
+    fn field_is_empty(&self, path: &[&str]) -> Option<bool> {
+        self.as_ref().and_then(|v| v.field_is_empty(path))
+    }
+
     fn is_none(&self) -> bool {
         self.is_none()
     }
@@ -124,6 +253,61 @@ impl<T: Matchable + 'static> Matchable for Option<T> {
     }
 }
 
+#[cfg(feature = "json_condition")]
+impl Matchable for serde_json::Value {
+    fn get_length(&self) -> Option<usize> {
+        match self {
+            serde_json::Value::String(s) => Some(s.len()),
+            serde_json::Value::Array(items) => Some(items.len()),
+            serde_json::Value::Object(map) => Some(map.len()),
+            _ => None,
+        }
+    }
+
+    fn get_field(&self, field: &str) -> Option<&dyn Any> {
+        match self {
+            serde_json::Value::Object(map) => map.get(field).map(|v| v as &dyn Any),
+            serde_json::Value::Array(items) => field
+                .parse::<usize>()
+                .ok()
+                .and_then(|idx| items.get(idx))
+                .map(|v| v as &dyn Any),
+            _ => None,
+        }
+    }
+
+    fn get_field_path(&self, path: &[&str]) -> Option<&dyn Any> {
+        let mut current = self;
+        for segment in path {
+            current = match current {
+                serde_json::Value::Object(map) => map.get(*segment)?,
+                serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+                _ => return None,
+            };
+        }
+        Some(current as &dyn Any)
+    }
+
+    fn get_field_elements(&self, path: &[&str]) -> Option<Vec<&dyn dynamic::DynMatchable>> {
+        let items = match path {
+            [] => self.as_array()?,
+            _ => self.get_field_path(path)?.downcast_ref::<serde_json::Value>()?.as_array()?,
+        };
+        Some(items.iter().map(|v| v as &dyn dynamic::DynMatchable).collect())
+    }
+
+    fn is_empty(&self) -> Option<bool> {
+        match self {
+            serde_json::Value::Null => Some(true),
+            _ => self.get_length().map(|len| len == 0),
+        }
+    }
+
+    fn is_none(&self) -> bool {
+        self.is_null()
+    }
+}
+
 // Implement for primitive types
 macro_rules! impl_matchable_primitive {
     ($($t:ty),*) => {
@@ -136,3 +320,9 @@ macro_rules! impl_matchable_primitive {
 impl_matchable_primitive!(
     i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char
 );
+
+#[cfg(feature = "decimal")]
+impl_matchable_primitive!(rust_decimal::Decimal);
+
+#[cfg(feature = "chrono")]
+impl_matchable_primitive!(chrono::DateTime<chrono::Utc>, chrono::NaiveDate, chrono::Duration);
@@ -1,10 +1,19 @@
 //! JSON-based matcher implementation.
 
+use std::fmt;
+#[cfg(feature = "chrono")]
+use std::sync::Arc;
+
+#[cfg(feature = "chrono")]
+use crate::temporal::{self, Clock, ClockGuard};
 use crate::{
-    condition::{ConditionMode, JsonNestedCondition},
-    evaluators::JsonEvaluator,
+    condition::{ConditionMode, JsonNestedCondition, Tolerance},
+    dsl::parse_condition,
+    error::{MatchError, ParseError, ValidationError},
+    evaluators::{JsonEvaluator, PreparedCondition},
     matchable::Matchable,
     result::JsonEvalResult,
+    schema::Schema,
     traits::{Evaluate, Matcher},
 };
 
@@ -23,30 +32,145 @@ use crate::{
 /// // Evaluate against a Matchable type
 /// assert!(matcher.matches(&user));
 /// ```
-#[derive(Debug, Clone)]
-pub struct JsonMatcher(pub JsonNestedCondition);
+///
+/// Operand data such as `in` sets and regex patterns is prepared once at
+/// construction and reused by every evaluation.
+///
+/// # Invalid operands
+///
+/// Constructors only check that the condition is well formed. A rule whose
+/// operand can't be prepared, such as an invalid regex pattern, is accepted
+/// and then fails with an error in its
+/// [`JsonConditionResult`](crate::JsonConditionResult) on every evaluation.
+/// Call [`JsonMatcher::compile`] after loading to reject it up front:
+///
+/// ```rust,ignore
+/// let matcher = JsonMatcher::from_json(json)?.compile()?;
+/// ```
+///
+/// # Migrating from the tuple struct
+///
+/// `JsonMatcher` used to be a tuple struct with a public condition. Construct
+/// it with [`JsonMatcher::new`] or [`From<JsonNestedCondition>`] instead of
+/// `JsonMatcher(condition)`, and read the condition with
+/// [`JsonMatcher::condition`] instead of `.0`.
+#[derive(Clone)]
+pub struct JsonMatcher {
+    condition: JsonNestedCondition,
+    prepared: PreparedCondition,
+    /// Clock for relative times like `"now-7d"`; the thread's clock if unset
+    #[cfg(feature = "chrono")]
+    clock: Option<Arc<dyn Clock>>,
+}
 
 impl JsonMatcher {
     /// Parse a matcher from a JSON string.
+    ///
+    /// Invalid operands are only reported by [`compile`](Self::compile).
     pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
         let condition: JsonNestedCondition = serde_json::from_str(json)?;
-        Ok(JsonMatcher(condition))
+        Ok(Self::from_condition(condition))
     }
 
     /// Parse a matcher from a serde_json::Value.
+    ///
+    /// Invalid operands are only reported by [`compile`](Self::compile).
     pub fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
         let condition: JsonNestedCondition = serde_json::from_value(value)?;
-        Ok(JsonMatcher(condition))
+        Ok(Self::from_condition(condition))
+    }
+
+    /// Parse a matcher from a text expression.
+    ///
+    /// See the [`parse_condition`](crate::parse_condition) docs for the syntax.
+    /// Invalid operands are only reported by [`compile`](Self::compile).
+    pub fn from_dsl(expression: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_condition(parse_condition(expression)?))
+    }
+
+    /// Create a matcher for `condition`.
+    pub fn new(condition: JsonNestedCondition) -> Self {
+        let prepared = PreparedCondition::new(&condition);
+        JsonMatcher {
+            condition,
+            prepared,
+            #[cfg(feature = "chrono")]
+            clock: None,
+        }
     }
 
     /// Create from an existing JsonNestedCondition.
     pub fn from_condition(condition: JsonNestedCondition) -> Self {
-        JsonMatcher(condition)
+        Self::new(condition)
+    }
+
+    /// Resolve relative times like `"now-7d"` against `clock` in every
+    /// evaluation of this matcher, on any thread.
+    #[cfg(feature = "chrono")]
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Some(Arc::new(clock));
+        self
+    }
+
+    /// Install the matcher's clock, if any, for one evaluation.
+    #[cfg(feature = "chrono")]
+    fn use_clock(&self) -> Option<ClockGuard> {
+        self.clock.clone().map(temporal::scoped_clock)
+    }
+
+    /// Compare floats as equal when they are within `tolerance` of each other,
+    /// in rules that don't set their own `tolerance` or `relative_tolerance`.
+    ///
+    /// By default floats must be exactly equal. Integers are always compared exactly.
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.prepared = self.prepared.with_tolerance(tolerance);
+        self
+    }
+
+    /// Check that every operand was prepared successfully.
+    ///
+    /// Returns the first [`MatchError::RegexError`] for an invalid pattern, or
+    /// [`MatchError::InvalidOperand`] for an element condition that doesn't parse.
+    /// Without this check, a rule with an invalid operand fails with an error
+    /// in its [`JsonConditionResult`](crate::JsonConditionResult).
+    pub fn compile(self) -> Result<Self, MatchError> {
+        match self.prepared.first_error() {
+            Some(err) => Err(err.clone()),
+            None => Ok(self),
+        }
+    }
+
+    /// Check every rule against a field schema.
+    ///
+    /// Rejects unknown fields, operators that don't apply to a field's type and
+    /// operands of the wrong type. See [`Schema::validate`].
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<ValidationError>> {
+        schema.validate(&self.condition)
     }
 
     /// Get a reference to the underlying condition.
     pub fn condition(&self) -> &JsonNestedCondition {
-        &self.0
+        &self.condition
+    }
+
+    /// Consume the matcher, returning the underlying condition.
+    pub fn into_condition(self) -> JsonNestedCondition {
+        self.condition
+    }
+}
+
+impl From<JsonNestedCondition> for JsonMatcher {
+    fn from(condition: JsonNestedCondition) -> Self {
+        Self::new(condition)
+    }
+}
+
+impl fmt::Debug for JsonMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonMatcher")
+            .field("condition", &self.condition)
+            .field("prepared", &self.prepared)
+            .finish_non_exhaustive()
     }
 }
 
@@ -55,7 +179,7 @@ impl serde::Serialize for JsonMatcher {
     where
         S: serde::Serializer,
     {
-        self.0.serialize(serializer)
+        self.condition.serialize(serializer)
     }
 }
 
@@ -65,17 +189,19 @@ impl<'de> serde::Deserialize<'de> for JsonMatcher {
         D: serde::Deserializer<'de>,
     {
         let condition = JsonNestedCondition::deserialize(deserializer)?;
-        Ok(JsonMatcher(condition))
+        Ok(Self::from_condition(condition))
     }
 }
 
 impl<T: Matchable> Matcher<T> for JsonMatcher {
     fn matches(&self, value: &T) -> bool {
-        JsonEvaluator::evaluate(&self.0, value).matched
+        #[cfg(feature = "chrono")]
+        let _clock = self.use_clock();
+        JsonEvaluator::evaluate_prepared(&self.condition, &self.prepared, value).matched
     }
 
     fn mode(&self) -> ConditionMode {
-        self.0.mode
+        self.condition.mode
     }
 }
 
@@ -83,7 +209,9 @@ impl<T: Matchable> Evaluate<T> for JsonMatcher {
     type Output = JsonEvalResult;
 
     fn evaluate(&self, value: &T) -> JsonEvalResult {
-        JsonEvaluator::evaluate(&self.0, value)
+        #[cfg(feature = "chrono")]
+        let _clock = self.use_clock();
+        JsonEvaluator::evaluate_prepared(&self.condition, &self.prepared, value)
     }
 }
 
@@ -2,6 +2,7 @@
 
 use crate::{
     condition::{Condition, ConditionMode},
+    error::MatchError,
     matchable::Matchable,
     result::{ConditionResult, MatchResult},
     traits::{Evaluate, Matcher, Predicate},
@@ -70,6 +71,22 @@ impl<'a, T: Matchable + 'static> RuleMatcher<'a, T> {
         self.conditions.extend(conditions);
         self
     }
+
+    /// Pre-compile every condition operand, such as `Regex` patterns.
+    ///
+    /// Call this once after building the matcher so that invalid patterns are
+    /// reported up front and evaluations reuse the compiled operands.
+    /// See [`Condition::compile`].
+    pub fn compile(self) -> Result<Self, MatchError> {
+        Ok(Self {
+            mode: self.mode,
+            conditions: self
+                .conditions
+                .into_iter()
+                .map(Condition::compile)
+                .collect::<Result<_, _>>()?,
+        })
+    }
 }
 
 impl<'a, T: Matchable + 'static> Matcher<T> for RuleMatcher<'a, T> {
@@ -1,4 +1,7 @@
-use crate::{condition::{ConditionMode, ConditionOperator}, error::MatchError};
+use crate::{condition::ConditionMode, error::MatchError};
+
+#[cfg(feature = "json_condition")]
+use crate::condition::ConditionOperator;
 
 /// Result of a match operation with detailed information
 #[derive(Debug, Clone)]
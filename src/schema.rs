@@ -0,0 +1,506 @@
+//! Field schemas describing the shape of a matchable type.
+//!
+//! A [`Schema`] maps field names to a broad [`FieldKind`] so that conditions
+//! loaded at runtime can be checked before they are evaluated. Schemas can be
+//! written by hand or built from the [`FieldInfo`] emitted by the derive macro.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::matchable::Matchable;
+#[cfg(feature = "json_condition")]
+use crate::{
+    condition::{ConditionOperator, JsonCondition, JsonField, JsonNestedCondition},
+    error::ValidationError,
+    expression::Expression,
+};
+
+/// Broad category of a field's value.
+///
+/// The kind decides which operators apply to a field and which operand
+/// values they accept.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    /// `bool`
+    Bool,
+    /// Any integer or floating point type
+    Number,
+    /// `Decimal`; operands may also be numeric strings, which keep their
+    /// exact value in JSON
+    Decimal,
+    /// `String`, `&str` or `char`
+    String,
+    /// A sequence whose elements have the given kind
+    List(Box<FieldKind>),
+    /// A value with its own fields
+    Object(Schema),
+    /// A value of unknown shape; any operator and operand is accepted
+    Any,
+}
+
+impl FieldKind {
+    /// Classify a Rust type name such as `u64`, `Option<String>` or `Vec<f64>`.
+    ///
+    /// Types that can't be classified map to [`FieldKind::Any`].
+    pub fn from_type_name(type_name: &str) -> FieldKind {
+        Self::classify(type_name, None)
+    }
+
+    /// Like [`from_type_name`](Self::from_type_name), but the innermost unknown
+    /// type becomes [`FieldKind::Object`] with the given schema.
+    fn classify(type_name: &str, object: Option<&Schema>) -> FieldKind {
+        let mut name = type_name.trim().trim_start_matches('&');
+        if name.starts_with('\'')
+            && let Some((_, rest)) = name.split_once(' ')
+        {
+            name = rest;
+        }
+        let name = name.trim_start_matches("mut ").trim();
+
+        if let Some(inner) = name.strip_prefix('[').and_then(|n| n.strip_suffix(']')) {
+            let item = inner.split(';').next().unwrap_or(inner);
+            return FieldKind::List(Box::new(Self::classify(item, object)));
+        }
+
+        let (base, args) = match name.split_once('<') {
+            Some((base, rest)) => (base, rest.strip_suffix('>').unwrap_or(rest)),
+            None => (name, ""),
+        };
+        let base = base.rsplit("::").next().unwrap_or(base);
+
+        match base {
+            "bool" => FieldKind::Bool,
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+            | "u128" | "usize" | "f32" | "f64" => FieldKind::Number,
+            "Decimal" => FieldKind::Decimal,
+            "String" | "str" | "char" => FieldKind::String,
+            "Option" | "Box" => Self::classify(args, object),
+            "Vec" | "VecDeque" | "HashSet" | "BTreeSet" => {
+                FieldKind::List(Box::new(Self::classify(args, object)))
+            }
+            _ => match object {
+                Some(schema) => FieldKind::Object(schema.clone()),
+                None => FieldKind::Any,
+            },
+        }
+    }
+}
+
+impl fmt::Display for FieldKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldKind::Bool => write!(f, "bool"),
+            FieldKind::Number => write!(f, "number"),
+            FieldKind::Decimal => write!(f, "decimal"),
+            FieldKind::String => write!(f, "string"),
+            FieldKind::List(item) => write!(f, "list of {}", item),
+            FieldKind::Object(_) => write!(f, "object"),
+            FieldKind::Any => write!(f, "any"),
+        }
+    }
+}
+
+/// Static description of a field, as emitted by `#[derive(Matchable)]`.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldInfo {
+    /// Name accepted by `get_field`
+    pub name: &'static str,
+    /// Other names accepted by `get_field`
+    pub aliases: &'static [&'static str],
+    /// Rust type of the field as written in source, with `Option` unwrapped
+    pub type_name: &'static str,
+    /// Whether the field is an `Option`
+    pub optional: bool,
+    /// Field metadata of the inner type for `#[matchable(nested)]` fields
+    pub nested: Option<fn() -> &'static [FieldInfo]>,
+}
+
+impl PartialEq for FieldInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_visiting(other, &mut Vec::new())
+    }
+}
+
+impl FieldInfo {
+    /// Compare nested metadata by content, since function addresses aren't
+    /// stable. Pairs of field lists already being compared further up count as
+    /// equal, so self-referential types terminate.
+    fn eq_visiting(&self, other: &Self, visiting: &mut Vec<(*const FieldInfo, *const FieldInfo)>) -> bool {
+        if self.name != other.name
+            || self.aliases != other.aliases
+            || self.type_name != other.type_name
+            || self.optional != other.optional
+        {
+            return false;
+        }
+        let (a, b) = match (self.nested, other.nested) {
+            (None, None) => return true,
+            (Some(a), Some(b)) => (a(), b()),
+            _ => return false,
+        };
+        let pair = (a.as_ptr(), b.as_ptr());
+        if std::ptr::eq(a, b) || visiting.contains(&pair) {
+            return true;
+        }
+        visiting.push(pair);
+        let equal = a.len() == b.len() && a.iter().zip(b).all(|(x, y)| x.eq_visiting(y, visiting));
+        visiting.pop();
+        equal
+    }
+}
+
+impl Eq for FieldInfo {}
+
+/// Schema entry for a single field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSchema {
+    /// Kind of the field's value
+    pub kind: FieldKind,
+    /// Whether the field may be `None`/`null`
+    pub optional: bool,
+}
+
+/// Field name to type mapping for a matchable type.
+///
+/// # Example
+///
+/// ```rust
+/// use condition_matcher::{FieldKind, Schema};
+///
+/// let schema = Schema::new()
+///     .field("symbol", FieldKind::String)
+///     .field("price", FieldKind::Number)
+///     .optional_field("note", FieldKind::String);
+///
+/// assert_eq!(schema.get("price").unwrap().kind, FieldKind::Number);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schema {
+    fields: BTreeMap<String, FieldSchema>,
+}
+
+impl Schema {
+    /// Create an empty schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a schema from field metadata. Aliases become fields of their own.
+    ///
+    /// A nested type that contains itself, directly or through other types,
+    /// is described down to its first repetition; fields below that are
+    /// [`FieldKind::Any`].
+    pub fn from_fields(fields: &[FieldInfo]) -> Self {
+        Self::from_fields_visiting(fields, &mut vec![fields.as_ptr()])
+    }
+
+    fn from_fields_visiting(fields: &[FieldInfo], visiting: &mut Vec<*const FieldInfo>) -> Self {
+        let mut schema = Self::new();
+        for info in fields {
+            let nested = info.nested.and_then(|nested| {
+                let nested = nested();
+                if visiting.contains(&nested.as_ptr()) {
+                    return None;
+                }
+                visiting.push(nested.as_ptr());
+                let schema = Self::from_fields_visiting(nested, visiting);
+                visiting.pop();
+                Some(schema)
+            });
+            let kind = FieldKind::classify(info.type_name, nested.as_ref());
+            for alias in info.aliases {
+                schema.insert(*alias, kind.clone(), info.optional);
+            }
+            schema.insert(info.name, kind, info.optional);
+        }
+        schema
+    }
+
+    /// Build the schema of a type from its derived [`Matchable::field_info`].
+    pub fn of<T: Matchable>() -> Self {
+        Self::from_fields(T::field_info())
+    }
+
+    /// Add a required field.
+    pub fn field(mut self, name: impl Into<String>, kind: FieldKind) -> Self {
+        self.insert(name, kind, false);
+        self
+    }
+
+    /// Add a field that may be `None`/`null`.
+    pub fn optional_field(mut self, name: impl Into<String>, kind: FieldKind) -> Self {
+        self.insert(name, kind, true);
+        self
+    }
+
+    /// Add or replace a field.
+    pub fn insert(&mut self, name: impl Into<String>, kind: FieldKind, optional: bool) {
+        self.fields.insert(name.into(), FieldSchema { kind, optional });
+    }
+
+    /// Look up a top-level field.
+    pub fn get(&self, name: &str) -> Option<&FieldSchema> {
+        self.fields.get(name)
+    }
+
+    /// Look up a field by dotted path, descending into objects and lists.
+    ///
+    /// List elements are addressed by numeric segments, e.g. `tags.0`.
+    /// Everything below a field of kind [`FieldKind::Any`] resolves to `Any`.
+    pub fn resolve(&self, path: &str) -> Option<FieldSchema> {
+        let mut segments = path.split('.');
+        let mut current = self.get(segments.next()?)?.clone();
+        for segment in segments {
+            current = match current.kind {
+                FieldKind::Object(schema) => schema.get(segment)?.clone(),
+                FieldKind::List(item) if segment.parse::<usize>().is_ok() => FieldSchema {
+                    kind: *item,
+                    optional: false,
+                },
+                FieldKind::Any => return Some(current),
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
+    /// Iterate over the top-level fields in name order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &FieldSchema)> {
+        self.fields.iter().map(|(name, field)| (name.as_str(), field))
+    }
+
+    /// Check every rule of a JSON condition against this schema.
+    ///
+    /// Reports unknown fields, operators that don't apply to a field's kind and
+    /// operands of the wrong type. All problems are collected, not just the first.
+    #[cfg(feature = "json_condition")]
+    pub fn validate(&self, condition: &JsonNestedCondition) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        self.validate_group(condition, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    #[cfg(feature = "json_condition")]
+    fn validate_group(&self, group: &JsonNestedCondition, errors: &mut Vec<ValidationError>) {
+        for rule in &group.rules {
+            if let Err(err) = self.validate_rule(rule) {
+                errors.push(err);
+            }
+        }
+        for nested in &group.nested {
+            self.validate_group(nested, errors);
+        }
+    }
+
+    #[cfg(feature = "json_condition")]
+    fn validate_rule(&self, rule: &JsonCondition) -> Result<(), ValidationError> {
+        let field = match &rule.field {
+            JsonField::Path(path) => self.resolve(path).ok_or_else(|| {
+                ValidationError::UnknownField {
+                    field: path.clone(),
+                }
+            })?,
+            JsonField::Expression(expression) => {
+                self.validate_expression(rule, expression)?;
+                FieldSchema {
+                    kind: FieldKind::Number,
+                    optional: false,
+                }
+            }
+        };
+
+        if !operator_applies(&rule.operator, &field.kind) {
+            return Err(ValidationError::UnsupportedOperator {
+                field: rule.field.to_string(),
+                operator: rule.operator,
+                kind: field.kind.to_string(),
+            });
+        }
+
+        if let (JsonField::Path(_), Some(other)) = (&rule.field, rule.field_reference()) {
+            let Some(other) = self.resolve(other) else {
+                return Err(ValidationError::UnknownField {
+                    field: other.to_string(),
+                });
+            };
+            if !kinds_comparable(&field.kind, &other.kind) {
+                return Err(ValidationError::InvalidValue {
+                    field: rule.field.to_string(),
+                    operator: rule.operator,
+                    kind: field.kind.to_string(),
+                    value: rule.value.clone(),
+                });
+            }
+            return Ok(());
+        }
+
+        if let Some(expression) = rule.value_expression() {
+            let Ok(expression) = expression else {
+                return Err(ValidationError::InvalidValue {
+                    field: rule.field.to_string(),
+                    operator: rule.operator,
+                    kind: field.kind.to_string(),
+                    value: rule.value.clone(),
+                });
+            };
+            self.validate_expression(rule, &expression)?;
+            if !kinds_comparable(&field.kind, &FieldKind::Number) {
+                return Err(ValidationError::InvalidValue {
+                    field: rule.field.to_string(),
+                    operator: rule.operator,
+                    kind: field.kind.to_string(),
+                    value: rule.value.clone(),
+                });
+            }
+            return Ok(());
+        }
+
+        if !operand_matches(&rule.operator, &field, &rule.value) {
+            return Err(ValidationError::InvalidValue {
+                field: rule.field.to_string(),
+                operator: rule.operator,
+                kind: field.kind.to_string(),
+                value: rule.value.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check that every field an expression refers to exists and is numeric.
+    #[cfg(feature = "json_condition")]
+    fn validate_expression(
+        &self,
+        rule: &JsonCondition,
+        expression: &Expression,
+    ) -> Result<(), ValidationError> {
+        for name in expression.fields() {
+            let Some(field) = self.resolve(name) else {
+                return Err(ValidationError::UnknownField {
+                    field: name.to_string(),
+                });
+            };
+            if !matches!(field.kind, FieldKind::Number | FieldKind::Decimal | FieldKind::Any) {
+                return Err(ValidationError::UnsupportedOperator {
+                    field: name.to_string(),
+                    operator: rule.operator,
+                    kind: field.kind.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether an operator can be evaluated against a field of the given kind.
+#[cfg(feature = "json_condition")]
+fn operator_applies(operator: &ConditionOperator, kind: &FieldKind) -> bool {
+    use ConditionOperator::*;
+
+    if *kind == FieldKind::Any {
+        return true;
+    }
+    match operator {
+        Equals | NotEquals | In | NotIn | IsNone | IsSome | Exists | NotExists => true,
+        GreaterThan | LessThan | GreaterThanOrEqual | LessThanOrEqual | Between => {
+            matches!(kind, FieldKind::Number | FieldKind::Decimal | FieldKind::String)
+        }
+        Contains | NotContains | StartsWith | EndsWith | Regex => *kind == FieldKind::String,
+        IsEmpty | IsNotEmpty => matches!(
+            kind,
+            FieldKind::String | FieldKind::List(_) | FieldKind::Object(_)
+        ),
+        ContainsAny | ContainsAll | AnyElement | AllElements => matches!(kind, FieldKind::List(_)),
+    }
+}
+
+/// Whether a rule's operand has the right shape for the operator and field.
+#[cfg(feature = "json_condition")]
+fn operand_matches(
+    operator: &ConditionOperator,
+    field: &FieldSchema,
+    value: &serde_json::Value,
+) -> bool {
+    use ConditionOperator::*;
+
+    match operator {
+        IsNone | IsSome | Exists | NotExists | IsEmpty | IsNotEmpty => true,
+        In | NotIn => value
+            .as_array()
+            .is_some_and(|items| items.iter().all(|v| value_matches(field, v))),
+        Between => crate::evaluators::json_bounds(value).is_some_and(|(lower, upper)| {
+            [lower, upper].into_iter().all(|bound| match bound {
+                std::ops::Bound::Included(v) | std::ops::Bound::Excluded(v) => {
+                    value_matches(field, v)
+                }
+                std::ops::Bound::Unbounded => true,
+            })
+        }),
+        Contains | NotContains | StartsWith | EndsWith | Regex => value.is_string(),
+        ContainsAny | ContainsAll => {
+            let element = match &field.kind {
+                FieldKind::List(item) => FieldSchema {
+                    kind: (**item).clone(),
+                    optional: false,
+                },
+                _ => field.clone(),
+            };
+            value
+                .as_array()
+                .is_some_and(|items| items.iter().all(|v| value_matches(&element, v)))
+        }
+        AnyElement | AllElements => {
+            let Ok(condition) = serde_json::from_value::<JsonNestedCondition>(value.clone()) else {
+                return false;
+            };
+            match &field.kind {
+                FieldKind::List(item) => match item.as_ref() {
+                    FieldKind::Object(schema) => schema.validate(&condition).is_ok(),
+                    _ => true,
+                },
+                _ => true,
+            }
+        }
+        _ => value_matches(field, value),
+    }
+}
+
+/// Whether fields of the two kinds can be compared with each other.
+#[cfg(feature = "json_condition")]
+fn kinds_comparable(a: &FieldKind, b: &FieldKind) -> bool {
+    match (a, b) {
+        (FieldKind::Any, _) | (_, FieldKind::Any) => true,
+        (
+            FieldKind::Number | FieldKind::Decimal,
+            FieldKind::Number | FieldKind::Decimal,
+        ) => true,
+        (FieldKind::List(a), FieldKind::List(b)) => kinds_comparable(a, b),
+        // A scalar can be checked for membership in a list
+        (_, FieldKind::List(item)) => kinds_comparable(a, item),
+        _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+    }
+}
+
+/// Whether a JSON value can be compared with a field of the given schema.
+#[cfg(feature = "json_condition")]
+fn value_matches(field: &FieldSchema, value: &serde_json::Value) -> bool {
+    if value.is_null() {
+        return field.optional || field.kind == FieldKind::Any;
+    }
+    match &field.kind {
+        FieldKind::Bool => value.is_boolean(),
+        FieldKind::Number => value.is_number(),
+        #[cfg(feature = "decimal")]
+        FieldKind::Decimal => crate::evaluators::json_to_decimal(value).is_some(),
+        #[cfg(not(feature = "decimal"))]
+        FieldKind::Decimal => value.is_number(),
+        FieldKind::String => value.is_string(),
+        FieldKind::List(_) => value.is_array(),
+        FieldKind::Object(_) => value.is_object(),
+        FieldKind::Any => true,
+    }
+}
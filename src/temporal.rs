@@ -0,0 +1,198 @@
+//! Date and time support for the `chrono` feature.
+//!
+//! `DateTime<Utc>`, `NaiveDate` and `Duration` fields compare with the usual
+//! operators. In JSON conditions, dates are written as RFC 3339 strings
+//! (`"2024-05-01T12:00:00Z"`), plain dates (`"2024-05-01"`) or expressions
+//! relative to the current time (`"now"`, `"now-7d"`, `"now+1h30m"`), and
+//! durations as strings like `"90m"` or a number of seconds.
+//!
+//! Relative expressions are resolved against the system clock. To make
+//! evaluations deterministic, give a matcher its own [`Clock`] with
+//! `JsonMatcher::with_clock`, or replace the clock for the current thread
+//! with [`set_clock`] until the returned guard is dropped:
+//!
+//! ```rust
+//! use chrono::{TimeZone, Utc};
+//! use condition_matcher::{set_clock, FixedClock};
+//!
+//! let clock = set_clock(FixedClock(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap()));
+//! assert_eq!(condition_matcher::now().to_rfc3339(), "2024-05-01T00:00:00+00:00");
+//! drop(clock);
+//! ```
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Source of the current time used by relative expressions such as `"now-7d"`.
+pub trait Clock: Send + Sync {
+    /// The current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system clock. This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that always returns the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl<F: Fn() -> DateTime<Utc> + Send + Sync> Clock for F {
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+thread_local! {
+    static CLOCK: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+/// Restores the previous clock of the current thread when dropped.
+///
+/// Returned by [`set_clock`].
+#[must_use = "the clock is restored as soon as the guard is dropped"]
+pub struct ClockGuard {
+    previous: Option<Arc<dyn Clock>>,
+    // The guard restores the clock of the thread that created it
+    _thread: PhantomData<*const ()>,
+}
+
+impl Drop for ClockGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CLOCK.with(|clock| *clock.borrow_mut() = previous);
+    }
+}
+
+/// Replace the clock used to resolve relative expressions on the current
+/// thread, until the returned guard is dropped.
+pub fn set_clock(new_clock: impl Clock + 'static) -> ClockGuard {
+    scoped_clock(Arc::new(new_clock))
+}
+
+/// Like [`set_clock`], for a clock that is already shared.
+pub(crate) fn scoped_clock(new_clock: Arc<dyn Clock>) -> ClockGuard {
+    ClockGuard {
+        previous: CLOCK.with(|clock| clock.replace(Some(new_clock))),
+        _thread: PhantomData,
+    }
+}
+
+/// The current time according to the current thread's clock.
+pub fn now() -> DateTime<Utc> {
+    CLOCK
+        .with(|clock| clock.borrow().as_ref().map(|c| c.now()))
+        .unwrap_or_else(Utc::now)
+}
+
+/// Parse a duration such as `"7d"`, `"-1h30m"` or `"45s"`.
+///
+/// Units are `w`, `d`, `h`, `m`, `s` and `ms`; a leading sign applies to the
+/// whole duration.
+pub(crate) fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let (negative, mut rest) = match text.as_bytes().first()? {
+        b'-' => (true, &text[1..]),
+        b'+' => (false, &text[1..]),
+        _ => (false, text),
+    };
+    if rest.is_empty() {
+        return None;
+    }
+
+    let mut total = Duration::zero();
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount: i64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let part = match &rest[..unit] {
+            "w" => Duration::try_weeks(amount)?,
+            "d" => Duration::try_days(amount)?,
+            "h" => Duration::try_hours(amount)?,
+            "m" => Duration::try_minutes(amount)?,
+            "s" => Duration::try_seconds(amount)?,
+            "ms" => Duration::try_milliseconds(amount)?,
+            _ => return None,
+        };
+        rest = &rest[unit..];
+        total = total.checked_add(&part)?;
+    }
+    Some(if negative { -total } else { total })
+}
+
+/// Parse an RFC 3339 timestamp or a relative expression like `"now-7d"`.
+pub(crate) fn parse_datetime(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Some(offset) = text.strip_prefix("now") {
+        let now = now();
+        return if offset.is_empty() {
+            Some(now)
+        } else if offset.starts_with(['+', '-']) {
+            now.checked_add_signed(parse_duration(offset)?)
+        } else {
+            None
+        };
+    }
+    DateTime::parse_from_rfc3339(text)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+/// Whether `text` is relative to the current time, like `"now-7d"`.
+#[cfg(feature = "json_condition")]
+pub(crate) fn is_relative(text: &str) -> bool {
+    text.trim().starts_with("now")
+}
+
+/// Parse a `YYYY-MM-DD` date, or the date part of a timestamp or relative expression.
+pub(crate) fn parse_date(text: &str) -> Option<NaiveDate> {
+    text.trim()
+        .parse::<NaiveDate>()
+        .ok()
+        .or_else(|| parse_datetime(text).map(|dt| dt.date_naive()))
+}
+
+/// Parse a JSON operand as a timestamp.
+#[cfg(feature = "json_condition")]
+pub(crate) fn json_to_datetime(value: &serde_json::Value) -> Option<DateTime<Utc>> {
+    parse_datetime(value.as_str()?)
+}
+
+/// Parse a JSON operand as a date.
+#[cfg(feature = "json_condition")]
+pub(crate) fn json_to_date(value: &serde_json::Value) -> Option<NaiveDate> {
+    parse_date(value.as_str()?)
+}
+
+/// Parse a JSON operand as a duration: a duration string or a number of seconds.
+#[cfg(feature = "json_condition")]
+pub(crate) fn json_to_duration(value: &serde_json::Value) -> Option<Duration> {
+    match value {
+        serde_json::Value::String(s) => parse_duration(s),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(secs) => Duration::try_seconds(secs),
+            None => Duration::try_milliseconds((n.as_f64()? * 1000.0).round() as i64),
+        },
+        _ => None,
+    }
+}
@@ -3,11 +3,12 @@
 // ============================================================================
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use crate::{
         builder::{field, MatcherBuilder},
         condition::ConditionMode,
-        condition::{Condition, ConditionOperator, ConditionSelector},
+        condition::{Condition, ConditionOperator, ConditionSelector, NestedCondition},
         matchers::RuleMatcher,
         traits::{Evaluate, Matcher},
         Matchable, MatchableDerive,
@@ -343,6 +344,145 @@ mod tests {
         assert!(!matcher.matches(&profile_without_nick));
     }
 
+    #[test]
+    fn test_owned_conditions() {
+        use std::collections::HashMap;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            tier: String,
+            balance: u64,
+        }
+
+        fn build_matcher(tier: &str, min_balance: u64) -> RuleMatcher<'static, Account> {
+            let mut matcher = RuleMatcher::new(ConditionMode::AND);
+            matcher
+                .add_condition(Condition::owned_field(
+                    "tier",
+                    ConditionOperator::Equals,
+                    tier.to_string(),
+                ))
+                .add_condition(Condition::owned_path(
+                    ["balance"],
+                    ConditionOperator::GreaterThanOrEqual,
+                    min_balance,
+                ));
+            matcher
+        }
+
+        let mut cache: HashMap<String, RuleMatcher<'static, Account>> = HashMap::new();
+        cache.insert("gold".to_string(), build_matcher("gold", 1_000));
+
+        let account = Account {
+            tier: "gold".to_string(),
+            balance: 2_500,
+        };
+
+        let handle = std::thread::spawn(move || {
+            let matcher = &cache["gold"];
+            matcher.matches(&account)
+        });
+        assert!(handle.join().unwrap());
+    }
+
+    #[test]
+    fn test_in_operator() {
+        use std::collections::HashSet;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Ticket {
+            status: String,
+            priority: u8,
+        }
+
+        let ticket = Ticket {
+            status: "pending".to_string(),
+            priority: 2,
+        };
+
+        let statuses = vec!["open", "pending", "review"];
+        let condition = field::<Ticket>("status").is_in(&statuses);
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(condition);
+        assert!(matcher.matches(&ticket));
+
+        let urgent: HashSet<u8> = [0, 1].into_iter().collect();
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Ticket>("priority").not_in(&urgent));
+        assert!(matcher.matches(&ticket));
+
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(Condition::owned_field(
+            "status",
+            ConditionOperator::In,
+            vec!["closed".to_string()],
+        ));
+        assert!(!matcher.matches(&ticket));
+    }
+
+    #[test]
+    fn test_in_operator_mixed_numeric() {
+        use std::collections::HashSet;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Ticket {
+            priority: u32,
+        }
+
+        let ticket = Ticket { priority: 2 };
+
+        let priorities = vec![1i64, 2];
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Ticket>("priority").is_in(&priorities));
+        assert!(matcher.matches(&ticket));
+
+        let urgent: HashSet<u8> = [0, 1].into_iter().collect();
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Ticket>("priority").not_in(&urgent));
+        assert!(matcher.matches(&ticket));
+
+        let fractions = vec![1.5f64, 2.5];
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Ticket>("priority").is_in(&fractions));
+        assert!(!matcher.matches(&ticket));
+    }
+
+    #[test]
+    fn test_between_operator() {
+        use std::ops::Bound;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Reading {
+            celsius: f64,
+            sensor: String,
+            count: u32,
+        }
+
+        let reading = Reading {
+            celsius: 21.5,
+            sensor: "kitchen".to_string(),
+            count: 10,
+        };
+
+        let comfortable = 18.0f64..=24.0;
+        let below_ten = 0u32..10;
+        let open_ended: (Bound<u32>, Bound<u32>) = (Bound::Excluded(5), Bound::Unbounded);
+        let sensors = "a"..="l";
+
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Reading>("celsius").between(&comfortable))
+            .add_condition(field::<Reading>("count").between(&open_ended))
+            .add_condition(field::<Reading>("sensor").between(&sensors));
+        assert!(matcher.matches(&reading));
+
+        let mut matcher = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Reading>("count").between(&below_ten));
+        let result = matcher.evaluate(&reading);
+        assert!(!result.is_match());
+        assert_eq!(result.condition_results[0].expected_value.as_deref(), Some("[0, 10)"));
+    }
+
     #[cfg(feature = "regex")]
     #[test]
     fn test_regex_matching() {
@@ -370,6 +510,106 @@ mod tests {
         assert!(!matcher.matches(&bad_email));
     }
 
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_compile() {
+        use crate::error::MatchError;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Email {
+            address: String,
+        }
+
+        let email = Email {
+            address: "user@example.com".to_string(),
+        };
+
+        let mut matcher: RuleMatcher<Email> = RuleMatcher::new(ConditionMode::OR);
+        matcher
+            .add_condition(Condition::owned_field("address", ConditionOperator::Regex, "^admin@".to_string()))
+            .add_condition(Condition {
+                selector: ConditionSelector::Not(Box::new(Condition {
+                    selector: ConditionSelector::FieldValue("address", &r"@example\.com$"),
+                    operator: ConditionOperator::Regex,
+                })),
+                operator: ConditionOperator::Equals,
+            });
+        let matcher = matcher.compile().unwrap();
+        assert!(matches!(
+            &matcher.conditions[0].selector,
+            ConditionSelector::OwnedFieldValue(_, expected) if expected.is::<regex::Regex>()
+        ));
+        assert!(!matcher.matches(&email));
+        assert!(matcher.matches(&Email {
+            address: "admin@example.org".to_string(),
+        }));
+
+        let mut invalid: RuleMatcher<Email> = RuleMatcher::new(ConditionMode::AND);
+        invalid.add_condition(Condition {
+            selector: ConditionSelector::FieldValue("address", &"(unclosed"),
+            operator: ConditionOperator::Regex,
+        });
+        assert!(matches!(
+            invalid.compile(),
+            Err(MatchError::RegexError { pattern, .. }) if pattern == "(unclosed"
+        ));
+    }
+
+    #[cfg(all(feature = "regex", feature = "json_condition"))]
+    #[test]
+    fn test_json_regex_compile() {
+        use crate::error::MatchError;
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Email {
+            address: String,
+        }
+
+        let email = Email {
+            address: "user@example.com".to_string(),
+        };
+
+        let matcher = JsonMatcher::from_dsl(r#"address regex "^[a-z]+@example\\.com$""#)
+            .unwrap()
+            .compile()
+            .unwrap();
+        assert!(matcher.matches(&email));
+
+        let invalid = JsonMatcher::from_dsl(r#"address == "x" || (address regex "[z-a]")"#).unwrap();
+        let result = invalid.evaluate(&email);
+        assert!(!result.matched);
+        assert!(result.details[1].error.as_deref().unwrap().contains("[z-a]"));
+        assert!(matches!(
+            invalid.compile(),
+            Err(MatchError::RegexError { pattern, .. }) if pattern == "[z-a]"
+        ));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_matcher_from_condition() {
+        use crate::{dsl::parse_condition, matchers::JsonMatcher};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Email {
+            address: String,
+        }
+
+        let email = Email {
+            address: "user@example.com".to_string(),
+        };
+        let condition = parse_condition(r#"address ends_with "@example.com""#).unwrap();
+
+        let matcher = JsonMatcher::new(condition.clone());
+        assert_eq!(matcher.condition().to_string(), condition.to_string());
+        assert!(matcher.matches(&email));
+
+        let matcher: JsonMatcher = condition.clone().into();
+        assert!(matcher.matches(&email));
+        assert_eq!(matcher.into_condition().to_string(), condition.to_string());
+    }
+
     // ========================================================================
     // New tests for the trait-based API
     // ========================================================================
@@ -462,6 +702,2312 @@ mod tests {
         assert!(!matcher2.matches(&product));
     }
 
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_value_matchable() {
+        use crate::matchers::JsonMatcher;
+
+        let payload = serde_json::json!({
+            "event": "order.created",
+            "order": {
+                "total": 129.5,
+                "items": [{"sku": "A-1", "qty": 2}, {"sku": "B-7", "qty": 1}],
+                "coupon": null
+            },
+            "tags": []
+        });
+
+        let sku = payload
+            .get_field_path(&["order", "items", "1", "sku"])
+            .and_then(|v| v.downcast_ref::<serde_json::Value>());
+        assert_eq!(sku, Some(&serde_json::json!("B-7")));
+        assert!(payload.get_field_path(&["order", "items", "5"]).is_none());
+        assert!(payload["order"]["coupon"].is_none());
+        assert_eq!(payload["order"]["items"].get_length(), Some(2));
+        assert_eq!(payload["tags"].is_empty(), Some(true));
+
+        let json = r#"{
+            "mode": "AND",
+            "rules": [
+                {"field": "event", "operator": "starts_with", "value": "order."},
+                {"field": "order.total", "operator": "greater_than", "value": 100},
+                {"field": "order.items.0.qty", "operator": "equals", "value": 2}
+            ]
+        }"#;
+        let matcher = JsonMatcher::from_json(json).unwrap();
+        assert!(matcher.matches(&payload));
+
+        let mut rules: RuleMatcher<serde_json::Value> = RuleMatcher::new(ConditionMode::AND);
+        rules
+            .add_condition(Condition {
+                selector: ConditionSelector::FieldValue("event", &"order.created"),
+                operator: ConditionOperator::Equals,
+            })
+            .add_condition(Condition {
+                selector: ConditionSelector::FieldPath(&["order", "total"], &100.0f64),
+                operator: ConditionOperator::GreaterThan,
+            });
+        assert!(rules.matches(&payload));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_dsl_parsing() {
+        use crate::{condition::JsonNestedCondition, dsl::parse_condition, matchers::JsonMatcher};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Quote {
+            symbol: String,
+            price: f64,
+            volume: f64,
+            halted: bool,
+        }
+
+        let quote = Quote {
+            symbol: "ETHUSD".to_string(),
+            price: 150.0,
+            volume: 2_000_000.0,
+            halted: false,
+        };
+
+        let matcher = JsonMatcher::from_dsl(
+            r#"price >= 100 && (symbol starts_with "BTC" || volume > 1e6) && !halted == true"#,
+        )
+        .unwrap();
+        assert!(matcher.matches(&quote));
+
+        // && binds tighter than ||
+        let condition = parse_condition("price < 10 || price > 100 && volume < 5").unwrap();
+        assert_eq!(condition.mode, ConditionMode::OR);
+        assert_eq!(condition.rules.len(), 1);
+        assert_eq!(condition.nested[0].mode, ConditionMode::AND);
+        assert!(!JsonMatcher::from_condition(condition).matches(&quote));
+
+        // Round-trip through the pretty-printer
+        let source = r#"!(symbol == "BTC" ^ symbol == "ETH") && (price <= 99.5 || `volume` > 1000000.0) && halted == false"#;
+        let parsed: JsonNestedCondition = source.parse().unwrap();
+        let printed = parsed.to_string();
+        let reparsed: JsonNestedCondition = printed.parse().unwrap();
+        assert_eq!(reparsed.to_string(), printed);
+        assert_eq!(
+            serde_json::to_value(&reparsed).unwrap(),
+            serde_json::to_value(&parsed).unwrap()
+        );
+
+        // Errors point at the offending token
+        let err = parse_condition("price >= 100 &&\n  (volume > )").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 13));
+        let err = parse_condition("price ~= 1").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+        let err = parse_condition("(price > 1").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert!(parse_condition("price approx 1").is_err());
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_in_operator() {
+        use crate::{evaluators::JsonEvaluator, matchers::JsonMatcher};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Order {
+            status: String,
+            region_id: u64,
+            discount: f64,
+        }
+
+        let order = Order {
+            status: "review".to_string(),
+            region_id: 9_007_199_254_740_993,
+            discount: 0.25,
+        };
+
+        let ids: Vec<u64> = (0..1000).chain([9_007_199_254_740_993]).collect();
+        let json = serde_json::json!({
+            "mode": "AND",
+            "rules": [
+                {"field": "status", "operator": "in", "value": ["open", "pending", "review"]},
+                {"field": "region_id", "operator": "in", "value": ids},
+                {"field": "discount", "operator": "not_in", "value": [0, 0.5, 1.0]}
+            ]
+        });
+        let matcher = JsonMatcher::from_value(json).unwrap();
+        assert!(matcher.matches(&order));
+
+        // Unprepared evaluation behaves the same way
+        assert!(JsonEvaluator::evaluate(matcher.condition(), &order).matched);
+
+        let matcher = JsonMatcher::from_dsl(r#"status not_in ["open", "closed"] && discount in [0.25]"#).unwrap();
+        assert!(matcher.matches(&order));
+        assert_eq!(
+            matcher.condition().to_string(),
+            r#"status not_in ["open","closed"] && discount in [0.25]"#
+        );
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_between_operator() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            symbol: String,
+            price: f64,
+            volume: u64,
+        }
+
+        let asset = Asset {
+            symbol: "ETH".to_string(),
+            price: 100.0,
+            volume: 5_000,
+        };
+
+        let json = r#"{
+            "mode": "AND",
+            "rules": [
+                {"field": "price", "operator": "between", "value": {"min": 50, "max": 100}},
+                {"field": "volume", "operator": "between", "value": [1000, 10000]},
+                {"field": "symbol", "operator": "between", "value": {"min": "A", "max": "F"}}
+            ]
+        }"#;
+        assert!(JsonMatcher::from_json(json).unwrap().matches(&asset));
+
+        let exclusive = r#"{"rules": [{"field": "price", "operator": "between", "value": {"min": 50, "max": 100, "inclusive": [true, false]}}]}"#;
+        assert!(!JsonMatcher::from_json(exclusive).unwrap().matches(&asset));
+
+        let open_ended = r#"{"rules": [{"field": "volume", "operator": "between", "value": {"min": 1000}}]}"#;
+        assert!(JsonMatcher::from_json(open_ended).unwrap().matches(&asset));
+
+        let matcher = JsonMatcher::from_dsl("price between {min: 99.5, max: 100, inclusive: false}").unwrap();
+        assert!(!matcher.matches(&asset));
+        let printed = matcher.condition().to_string();
+        assert_eq!(JsonMatcher::from_dsl(&printed).unwrap().condition().to_string(), printed);
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_schema_validation() {
+        use crate::error::ValidationError;
+        use crate::matchers::JsonMatcher;
+        use crate::schema::{FieldKind, Schema};
+
+        let schema = Schema::new()
+            .field("symbol", FieldKind::String)
+            .field("current_price", FieldKind::Number)
+            .field("tags", FieldKind::List(Box::new(FieldKind::String)))
+            .optional_field(
+                "issuer",
+                FieldKind::Object(Schema::new().field("country", FieldKind::String)),
+            );
+
+        let valid = JsonMatcher::from_dsl(
+            r#"symbol starts_with "BTC" && current_price between [1, 10] && tags.0 in ["a", "b"] && (issuer == null || issuer.country == "US")"#,
+        )
+        .unwrap();
+        assert_eq!(valid.validate(&schema), Ok(()));
+
+        let invalid = JsonMatcher::from_dsl(
+            r#"curent_price > 1 || current_price starts_with "1" || symbol == 3 || issuer.country in ["US", 1] || issuer.city == "x""#,
+        )
+        .unwrap();
+        let errors = invalid.validate(&schema).unwrap_err();
+        assert_eq!(errors.len(), 5);
+        assert_eq!(
+            errors[0],
+            ValidationError::UnknownField {
+                field: "curent_price".to_string()
+            }
+        );
+        assert!(matches!(
+            &errors[1],
+            ValidationError::UnsupportedOperator { operator: ConditionOperator::StartsWith, kind, .. } if kind == "number"
+        ));
+        assert!(matches!(&errors[2], ValidationError::InvalidValue { field, .. } if field == "symbol"));
+        assert!(matches!(&errors[3], ValidationError::InvalidValue { operator: ConditionOperator::In, .. }));
+        assert_eq!(errors[4].to_string(), "Unknown field 'issuer.city'");
+    }
+
+    #[test]
+    fn test_derived_field_info() {
+        use crate::schema::{FieldInfo, FieldKind, Schema};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Listing {
+            title: &'static str,
+            price: f64,
+            tags: Vec<String>,
+            seller: Option<std::string::String>,
+        }
+
+        assert_eq!(
+            Listing::field_info(),
+            &[
+                FieldInfo { name: "title", aliases: &[], type_name: "&'static str", optional: false, nested: None },
+                FieldInfo { name: "price", aliases: &[], type_name: "f64", optional: false, nested: None },
+                FieldInfo { name: "tags", aliases: &[], type_name: "Vec<String>", optional: false, nested: None },
+                FieldInfo { name: "seller", aliases: &[], type_name: "std::string::String", optional: true, nested: None },
+            ]
+        );
+        assert!(<i32 as Matchable>::field_info().is_empty());
+
+        let schema = Schema::of::<Listing>();
+        assert_eq!(schema.get("title").unwrap().kind, FieldKind::String);
+        assert_eq!(schema.get("price").unwrap().kind, FieldKind::Number);
+        assert_eq!(schema.get("tags").unwrap().kind, FieldKind::List(Box::new(FieldKind::String)));
+        let seller = schema.get("seller").unwrap();
+        assert!(seller.optional);
+        assert_eq!(seller.kind, FieldKind::String);
+
+        #[cfg(feature = "json_condition")]
+        {
+            let matcher = crate::matchers::JsonMatcher::from_dsl(r#"title contains "bike" && prize < 100"#).unwrap();
+            assert_eq!(matcher.validate(&schema).unwrap_err().len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_derived_nested_paths() {
+        use crate::schema::{FieldKind, Schema};
+        use std::collections::HashMap;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Address {
+            city: String,
+            zip: Option<u32>,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Customer {
+            name: String,
+            #[matchable(nested)]
+            address: Address,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Item {
+            sku: String,
+            qty: u32,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Order {
+            id: u64,
+            #[matchable(nested)]
+            customer: Option<Box<Customer>>,
+            #[matchable(nested)]
+            items: Vec<Item>,
+            #[matchable(nested)]
+            by_sku: HashMap<String, Item>,
+            tags: Vec<String>,
+        }
+
+        let order = Order {
+            id: 7,
+            customer: Some(Box::new(Customer {
+                name: "Ada".to_string(),
+                address: Address {
+                    city: "London".to_string(),
+                    zip: None,
+                },
+            })),
+            items: vec![Item {
+                sku: "A-1".to_string(),
+                qty: 2,
+            }],
+            by_sku: HashMap::from([(
+                "B-2".to_string(),
+                Item {
+                    sku: "B-2".to_string(),
+                    qty: 5,
+                },
+            )]),
+            tags: vec!["rush".to_string()],
+        };
+
+        let city = order.get_field_path(&["customer", "address", "city"]);
+        assert_eq!(city.and_then(|v| v.downcast_ref::<String>()).map(String::as_str), Some("London"));
+        assert!(order.get_field_path(&["customer", "address", "zip"]).is_none());
+        assert_eq!(order.get_field_path(&["items", "0", "qty"]).and_then(|v| v.downcast_ref::<u32>()), Some(&2));
+        assert!(order.get_field_path(&["items", "1", "qty"]).is_none());
+        assert_eq!(order.get_field_path(&["by_sku", "B-2", "qty"]).and_then(|v| v.downcast_ref::<u32>()), Some(&5));
+        assert!(order.get_field_path(&["by_sku", "B-2"]).is_some_and(|v| v.is::<Item>()));
+        assert!(order.get_field_path(&["tags", "0"]).is_some_and(|v| v.is::<String>()));
+        assert!(order.get_field_path(&["id", "0"]).is_none());
+
+        let mut matcher: RuleMatcher<Order> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(Condition {
+                selector: ConditionSelector::FieldPath(&["customer", "address", "city"], &"London"),
+                operator: ConditionOperator::Equals,
+            })
+            .add_condition(Condition {
+                selector: ConditionSelector::FieldPath(&["items", "0", "qty"], &1u32),
+                operator: ConditionOperator::GreaterThan,
+            });
+        assert!(matcher.matches(&order));
+
+        let schema = Schema::of::<Order>();
+        assert_eq!(schema.resolve("customer.address.city").unwrap().kind, FieldKind::String);
+        assert_eq!(schema.resolve("items.0.qty").unwrap().kind, FieldKind::Number);
+        assert!(schema.resolve("customer.address.street").is_none());
+
+        #[cfg(feature = "json_condition")]
+        {
+            let matcher = crate::matchers::JsonMatcher::from_dsl(
+                r#"customer.address.city == "London" && items.0.sku starts_with "A" && by_sku.`B-2`.qty >= 5"#,
+            )
+            .unwrap();
+            assert!(matcher.matches(&order));
+        }
+    }
+
+    #[test]
+    fn test_derived_field_names() {
+        use crate::schema::Schema;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        #[matchable(rename_all = "camelCase")]
+        struct Quote {
+            #[matchable(rename = "price", alias = "px", alias = "last")]
+            current_price_usd: f64,
+            trade_count: u32,
+            #[matchable(skip)]
+            internal_id: u64,
+            r#type: String,
+        }
+
+        let quote = Quote {
+            current_price_usd: 101.5,
+            trade_count: 3,
+            internal_id: 99,
+            r#type: "spot".to_string(),
+        };
+
+        for name in ["price", "px", "last"] {
+            assert_eq!(quote.get_field(name).and_then(|v| v.downcast_ref::<f64>()), Some(&101.5));
+        }
+        assert!(quote.get_field("current_price_usd").is_none());
+        assert!(quote.get_field("tradeCount").is_some());
+        assert!(quote.get_field("trade_count").is_none());
+        assert!(quote.get_field("internalId").is_none());
+        assert!(quote.get_field("type").is_some());
+
+        let names: Vec<_> = Quote::field_info().iter().map(|f| f.name).collect();
+        assert_eq!(names, ["price", "tradeCount", "type"]);
+        assert_eq!(Quote::field_info()[0].aliases, &["px", "last"]);
+        assert!(Schema::of::<Quote>().get("px").is_some());
+
+        let mut matcher: RuleMatcher<Quote> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Quote>("px").gt(&100.0));
+        assert!(matcher.matches(&quote));
+
+        #[cfg(feature = "json_condition")]
+        {
+            let matcher = crate::matchers::JsonMatcher::from_dsl(r#"price > 100 && tradeCount == 3"#).unwrap();
+            assert!(matcher.matches(&quote));
+        }
+    }
+
+    #[test]
+    fn test_derived_enums_and_tuple_structs() {
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Pair(u32, #[matchable(rename = "label")] String);
+
+        let pair = Pair(4, "four".to_string());
+        assert_eq!(pair.get_field("0").and_then(|v| v.downcast_ref::<u32>()), Some(&4));
+        assert!(pair.get_field("label").is_some());
+        assert!(pair.get_field("1").is_none());
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        enum Side {
+            Buy,
+            Sell,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        #[matchable(tag = "kind")]
+        enum OrderType {
+            Market,
+            Limit { price: f64, expires: Option<u64> },
+            Stop(f64, #[matchable(skip)] u8),
+            #[matchable(rename = "trailing")]
+            TrailingStop { distance: f64 },
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Order {
+            #[matchable(nested)]
+            order_type: OrderType,
+            side: Side,
+        }
+
+        assert_eq!(Side::Sell.get_field("variant").and_then(|v| v.downcast_ref::<&str>()), Some(&"Sell"));
+        assert_ne!(Side::Buy, Side::Sell);
+
+        let limit = OrderType::Limit { price: 9.5, expires: None };
+        assert_eq!(limit.get_field("kind").and_then(|v| v.downcast_ref::<&str>()), Some(&"Limit"));
+        assert_eq!(limit.get_field("price").and_then(|v| v.downcast_ref::<f64>()), Some(&9.5));
+        assert!(limit.get_field("expires").is_none());
+        assert!(limit.get_field("0").is_none());
+        assert!(OrderType::Market.get_field("price").is_none());
+        assert!(OrderType::Stop(3.0, 1).get_field("0").is_some());
+        assert!(OrderType::Stop(3.0, 1).get_field("1").is_none());
+        let trailing = OrderType::TrailingStop { distance: 1.0 };
+        assert_eq!(trailing.get_field("kind").and_then(|v| v.downcast_ref::<&str>()), Some(&"trailing"));
+
+        let names: Vec<_> = OrderType::field_info().iter().map(|f| (f.name, f.optional)).collect();
+        assert_eq!(names, [("kind", false), ("price", true), ("expires", true), ("0", true), ("distance", true)]);
+
+        let mut matcher: RuleMatcher<OrderType> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<OrderType>("kind").equals(&"Limit"))
+            .add_condition(field::<OrderType>("price").lt(&10.0));
+        assert!(matcher.matches(&limit));
+        assert!(!matcher.matches(&OrderType::Market));
+
+        let order = Order { order_type: limit, side: Side::Buy };
+        assert!(order.get_field_path(&["order_type", "price"]).is_some());
+
+        #[cfg(feature = "json_condition")]
+        {
+            let matcher = crate::matchers::JsonMatcher::from_json(
+                r#"{"rules": [{"field": "kind", "operator": "equals", "value": "Limit"}]}"#,
+            )
+            .unwrap();
+            assert!(matcher.matches(&order.order_type));
+            assert!(crate::matchers::JsonMatcher::from_dsl(r#"order_type.kind == "Limit""#).unwrap().matches(&order));
+        }
+    }
+
+    #[test]
+    fn test_derived_virtual_fields() {
+        use crate::matchable::FieldValue;
+
+        fn mid_price(quote: &Quote) -> f64 {
+            (quote.bid + quote.ask) / 2.0
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        #[matchable(virtual(spread = "Self::spread", mid = "mid_price"))]
+        struct Quote {
+            bid: f64,
+            ask: f64,
+        }
+
+        impl Quote {
+            fn spread(&self) -> f64 {
+                self.ask - self.bid
+            }
+        }
+
+        let quote = Quote { bid: 99.0, ask: 101.0 };
+        assert!(quote.get_field("spread").is_none());
+        assert!(matches!(quote.get_field_value("spread"), Some(FieldValue::Owned(_))));
+        assert!(matches!(quote.get_field_value("bid"), Some(FieldValue::Borrowed(_))));
+        assert_eq!(
+            quote.get_field_value("mid").and_then(|v| v.as_any().downcast_ref::<f64>().copied()),
+            Some(100.0)
+        );
+        assert_eq!(Quote::field_info().last().map(|f| f.name), Some("mid"));
+
+        let mut matcher: RuleMatcher<Quote> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Quote>("spread").lte(&2.0));
+        assert!(matcher.matches(&quote));
+        assert!(!matcher.matches(&Quote { bid: 90.0, ask: 101.0 }));
+
+        #[cfg(feature = "json_condition")]
+        {
+            let matcher = crate::matchers::JsonMatcher::from_dsl("spread < 5 && mid == 100").unwrap();
+            assert!(matcher.matches(&quote));
+        }
+    }
+
+    #[test]
+    fn test_owned_field_values() {
+        use crate::matchable::FieldValue;
+        use std::any::Any;
+
+        /// Stores cents but exposes a converted dollar amount
+        #[derive(PartialEq, Debug)]
+        struct Price {
+            cents: i64,
+        }
+
+        impl Matchable for Price {
+            fn get_field(&self, field: &str) -> Option<&dyn Any> {
+                match field {
+                    "cents" => Some(&self.cents),
+                    _ => None,
+                }
+            }
+
+            fn get_field_value(&self, field: &str) -> Option<FieldValue<'_>> {
+                match field {
+                    "dollars" => Some(FieldValue::Owned(Box::new(self.cents as f64 / 100.0))),
+                    _ => self.get_field(field).map(FieldValue::Borrowed),
+                }
+            }
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        #[matchable(virtual(total = "Self::total"))]
+        struct Line {
+            qty: u32,
+            #[matchable(nested)]
+            price: Option<Price>,
+        }
+
+        impl Line {
+            fn total(&self) -> f64 {
+                self.price.as_ref().map_or(0.0, |p| p.cents as f64 / 100.0) * self.qty as f64
+            }
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Cart {
+            #[matchable(nested)]
+            lines: Vec<Line>,
+        }
+
+        let cart = Cart {
+            lines: vec![Line {
+                qty: 3,
+                price: Some(Price { cents: 250 }),
+            }],
+        };
+
+        let dollars = cart.get_field_path_value(&["lines", "0", "price", "dollars"]);
+        assert_eq!(dollars.as_ref().and_then(|v| v.as_any().downcast_ref::<f64>()), Some(&2.5));
+        assert!(cart.get_field_path(&["lines", "0", "price", "dollars"]).is_none());
+        assert!(matches!(
+            cart.get_field_path_value(&["lines", "0", "qty"]),
+            Some(FieldValue::Borrowed(_))
+        ));
+        assert!(cart.get_field_path_value(&["lines", "0", "total"]).is_some());
+
+        let mut matcher: RuleMatcher<Cart> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(Condition {
+                selector: ConditionSelector::FieldPath(&["lines", "0", "price", "dollars"], &2.5f64),
+                operator: ConditionOperator::Equals,
+            })
+            .add_condition(Condition {
+                selector: ConditionSelector::FieldPath(&["lines", "0", "total"], &7.0f64),
+                operator: ConditionOperator::GreaterThan,
+            });
+        assert!(matcher.matches(&cart));
+
+        #[cfg(feature = "json_condition")]
+        {
+            let matcher =
+                crate::matchers::JsonMatcher::from_dsl("lines.0.price.dollars < 3 && lines.0.total == 7.5").unwrap();
+            assert!(matcher.matches(&cart));
+        }
+    }
+
+    #[test]
+    fn test_custom_comparator() {
+        use crate::comparator::{unregister_comparator, Comparator};
+
+        /// Fixed-point amount with 4 decimal places
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        struct Fixed(i64);
+
+        /// Case-insensitive ticker, only equality is meaningful
+        #[derive(Debug, Clone, PartialEq)]
+        struct Ticker(String);
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Position {
+            size: Fixed,
+            ticker: Ticker,
+        }
+
+        let position = Position {
+            size: Fixed(15_000),
+            ticker: Ticker("btc".to_string()),
+        };
+        let one = Fixed(10_000);
+        let upper = Ticker("BTC".to_string());
+
+        let mut matcher: RuleMatcher<Position> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Position>("size").gt(&one))
+            .add_condition(field::<Position>("ticker").equals(&upper));
+        assert!(!matcher.matches(&position));
+
+        Comparator::<Fixed>::partial_ord()
+            .with_display(|f| format!("{:.4}", f.0 as f64 / 10_000.0))
+            .register();
+        Comparator::<Ticker>::new()
+            .with_equality(|a, b| a.0.eq_ignore_ascii_case(&b.0))
+            .register();
+
+        let result = matcher.evaluate(&position);
+        assert!(result.is_match());
+        assert_eq!(result.condition_results[0].actual_value.as_deref(), Some("1.5000"));
+
+        let mut ordering: RuleMatcher<Position> = RuleMatcher::new(ConditionMode::AND);
+        ordering.add_condition(field::<Position>("ticker").gt(&upper));
+        assert!(!ordering.matches(&position));
+
+        #[cfg(feature = "json_condition")]
+        {
+            use crate::matchers::JsonMatcher;
+
+            let matcher = JsonMatcher::from_dsl("size >= 1.5").unwrap();
+            assert!(!matcher.matches(&position));
+
+            Comparator::<Fixed>::partial_ord()
+                .with_json(|v| v.as_f64().map(|f| Fixed((f * 10_000.0).round() as i64)))
+                .register();
+            assert!(matcher.matches(&position));
+            assert!(!JsonMatcher::from_dsl("size < 1").unwrap().matches(&position));
+
+            // Programmatic conditions on JSON documents convert the document value
+            let doc = serde_json::json!({"size": 2.0});
+            let mut on_doc: RuleMatcher<serde_json::Value> = RuleMatcher::new(ConditionMode::AND);
+            on_doc.add_condition(field::<serde_json::Value>("size").gt(&one));
+            assert!(on_doc.matches(&doc));
+        }
+
+        unregister_comparator::<Fixed>();
+        assert!(!matcher.matches(&position));
+        unregister_comparator::<Ticker>();
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimal_values() {
+        use rust_decimal::Decimal;
+        use rust_decimal_macros::dec;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Invoice {
+            total: Decimal,
+            discount: Option<Decimal>,
+        }
+
+        let invoice = Invoice {
+            total: dec!(0.30),
+            discount: Some(dec!(19.99)),
+        };
+
+        let sum = dec!(0.1) + dec!(0.2);
+        let limit = dec!(20);
+        let mut matcher: RuleMatcher<Invoice> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Invoice>("total").equals(&sum))
+            .add_condition(field::<Invoice>("discount").lt(&limit));
+        assert!(matcher.matches(&invoice));
+
+        let range = dec!(0.3)..=dec!(0.31);
+        let members = vec![dec!(1), dec!(0.3)];
+        let mut matcher: RuleMatcher<Invoice> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Invoice>("total").between(&range))
+            .add_condition(field::<Invoice>("total").is_in(&members));
+        assert!(matcher.matches(&invoice));
+
+        #[cfg(feature = "json_condition")]
+        {
+            use crate::matchers::JsonMatcher;
+
+            let matches = |rule: serde_json::Value| {
+                let json = serde_json::json!({"mode": "AND", "rules": [rule]});
+                JsonMatcher::from_json(&json.to_string()).unwrap().matches(&invoice)
+            };
+
+            // 0.1 + 0.2 is exactly 0.3, unlike with f64
+            assert!(matches(serde_json::json!({"field": "total", "operator": "equals", "value": 0.3})));
+            assert!(!matches(serde_json::json!({"field": "total", "operator": "equals", "value": 0.30000000000000004})));
+            assert!(matches(serde_json::json!({"field": "total", "operator": "equals", "value": "0.300"})));
+            assert!(matches(serde_json::json!({"field": "discount", "operator": "greater_than", "value": "19.98"})));
+            assert!(!matches(serde_json::json!({"field": "discount", "operator": "less_than", "value": 19.99})));
+            assert!(matches(serde_json::json!({"field": "discount", "operator": "in", "value": [5, 19.99]})));
+            assert!(matches(serde_json::json!({"field": "discount", "operator": "not_in", "value": ["19.9"]})));
+            assert!(matches(serde_json::json!({"field": "total", "operator": "between", "value": {"min": 0.3, "max": "0.5", "inclusive": [true, false]}})));
+            assert!(!matches(serde_json::json!({"field": "total", "operator": "between", "value": {"min": 0.3, "inclusive": false}})));
+
+            // JSON documents compared against a Decimal operand
+            let doc = serde_json::json!({"price": "19.99", "qty": 3});
+            let price = dec!(19.99);
+            let qty = dec!(3.0);
+            let mut on_doc: RuleMatcher<serde_json::Value> = RuleMatcher::new(ConditionMode::AND);
+            on_doc
+                .add_condition(field::<serde_json::Value>("price").equals(&price))
+                .add_condition(field::<serde_json::Value>("qty").equals(&qty));
+            assert!(on_doc.matches(&doc));
+
+            // Numeric strings are valid operands for Decimal fields
+            use crate::error::ValidationError;
+            use crate::schema::{FieldKind, Schema};
+
+            let schema = Schema::of::<Invoice>();
+            assert_eq!(schema.get("total").unwrap().kind, FieldKind::Decimal);
+            let valid = JsonMatcher::from_dsl(
+                r#"total == "12.50" && discount in ["5", 19.99] && total between ["0.1", "1e2"] && total < {"$field": "discount"}"#,
+            )
+            .unwrap();
+            assert_eq!(valid.validate(&schema), Ok(()));
+            let invalid = JsonMatcher::from_dsl(r#"total == "12,50""#).unwrap();
+            let errors = invalid.validate(&schema).unwrap_err();
+            assert!(matches!(&errors[0], ValidationError::InvalidValue { field, .. } if field == "total"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn test_temporal_values() {
+        use crate::temporal::{parse_date, parse_datetime, parse_duration};
+        use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            last_login: DateTime<Utc>,
+            created: NaiveDate,
+            session: Duration,
+        }
+
+        let account = Account {
+            last_login: Utc.with_ymd_and_hms(2024, 4, 28, 9, 30, 0).unwrap(),
+            created: NaiveDate::from_ymd_opt(2023, 1, 15).unwrap(),
+            session: Duration::minutes(45),
+        };
+
+        assert_eq!(parse_duration("1h30m"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("-2w"), Some(Duration::weeks(-2)));
+        assert_eq!(parse_duration("5x"), None);
+        assert_eq!(parse_duration("-"), None);
+        assert_eq!(parse_date("2024-05-01"), NaiveDate::from_ymd_opt(2024, 5, 1));
+        assert_eq!(parse_datetime("nowish"), None);
+
+        let cutoff = Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+        let max_session = Duration::hours(1);
+        let mut matcher: RuleMatcher<Account> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Account>("last_login").gt(&cutoff))
+            .add_condition(field::<Account>("session").lt(&max_session));
+        assert!(matcher.matches(&account));
+
+        // String operands are parsed as the field's type
+        let mut matcher: RuleMatcher<Account> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Account>("last_login").gt(&"2024-04-01T00:00:00Z"))
+            .add_condition(field::<Account>("created").equals(&"2023-01-15"))
+            .add_condition(field::<Account>("session").gte(&"45m"));
+        assert!(matcher.matches(&account));
+
+        let year = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()..NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let mut matcher: RuleMatcher<Account> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Account>("created").between(&year));
+        assert!(matcher.matches(&account));
+
+        #[cfg(feature = "json_condition")]
+        {
+            use crate::matchers::JsonMatcher;
+            use crate::temporal::{now, set_clock, FixedClock};
+
+            let may_first = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+            let clock = set_clock(FixedClock(may_first));
+            assert_eq!(parse_datetime("now-7d"), Utc.with_ymd_and_hms(2024, 4, 24, 0, 0, 0).single());
+
+            let matches = |rule: serde_json::Value| {
+                let json = serde_json::json!({"mode": "AND", "rules": [rule]});
+                JsonMatcher::from_json(&json.to_string()).unwrap().matches(&account)
+            };
+
+            assert!(matches(serde_json::json!({"field": "last_login", "operator": "greater_than", "value": "now-7d"})));
+            assert!(!matches(serde_json::json!({"field": "last_login", "operator": "greater_than", "value": "now-1d"})));
+            assert!(matches(serde_json::json!({"field": "last_login", "operator": "equals", "value": "2024-04-28T11:30:00+02:00"})));
+            assert!(matches(serde_json::json!({"field": "last_login", "operator": "between", "value": ["now-30d", "now"]})));
+            assert!(!matches(serde_json::json!({"field": "last_login", "operator": "less_than", "value": "yesterday"})));
+            assert!(matches(serde_json::json!({"field": "created", "operator": "less_than_or_equal", "value": "2023-01-15"})));
+            assert!(matches(serde_json::json!({"field": "created", "operator": "in", "value": ["2023-01-15", "2023-02-01"]})));
+            assert!(matches(serde_json::json!({"field": "created", "operator": "in", "value": ["2020-01-01", "now-472d"]})));
+            assert!(matches(serde_json::json!({"field": "last_login", "operator": "in", "value": ["2024-01-01T00:00:00Z", "now-2d14h30m"]})));
+            assert!(matches(serde_json::json!({"field": "last_login", "operator": "not_in", "value": ["2024-01-01T00:00:00Z", "now-2d"]})));
+            assert!(matches(serde_json::json!({"field": "created", "operator": "less_than", "value": "now-365d"})));
+            assert!(matches(serde_json::json!({"field": "session", "operator": "equals", "value": 2700})));
+            assert!(matches(serde_json::json!({"field": "session", "operator": "greater_than", "value": "30m"})));
+            assert!(matches(serde_json::json!({"field": "session", "operator": "not_in", "value": ["1h", 60]})));
+
+            // Timestamps stored as strings in a JSON document
+            let doc = serde_json::json!({"created_at": "2024-04-30T12:00:00Z"});
+            let since = Utc.with_ymd_and_hms(2024, 4, 30, 0, 0, 0).unwrap();
+            let mut on_doc: RuleMatcher<serde_json::Value> = RuleMatcher::new(ConditionMode::AND);
+            on_doc.add_condition(field::<serde_json::Value>("created_at").gte(&since));
+            assert!(on_doc.matches(&doc));
+            let recent = JsonMatcher::from_dsl(r#"created_at > "now-1d""#).unwrap();
+            assert!(!recent.matches(&doc));
+
+            // Dropping the guard restores the system clock on this thread only
+            let elsewhere = std::thread::spawn(now).join().unwrap();
+            assert!(elsewhere > may_first + Duration::days(365));
+            drop(clock);
+            assert!(now() > may_first + Duration::days(365));
+
+            // A matcher's own clock applies on every thread that evaluates it
+            let recent = JsonMatcher::from_dsl(r#"last_login > "now-7d""#).unwrap();
+            let recent_then = recent.clone().with_clock(FixedClock(may_first));
+            let results = std::thread::scope(|scope| {
+                let then = scope.spawn(|| recent_then.matches(&account));
+                let today = scope.spawn(|| recent.matches(&account));
+                (then.join().unwrap(), today.join().unwrap())
+            });
+            assert_eq!(results, (true, false));
+            let _other = set_clock(FixedClock(may_first + Duration::days(30)));
+            assert!(recent_then.matches(&account));
+            assert!(!recent.matches(&account));
+        }
+    }
+
+    #[test]
+    fn test_mixed_numeric_comparisons() {
+        use crate::error::MatchError;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Holder {
+            age: u64,
+            balance: i128,
+            supply: u128,
+            ratio: f32,
+            name: String,
+        }
+
+        let holder = Holder {
+            age: 30,
+            balance: -5,
+            supply: u128::MAX,
+            ratio: 0.5,
+            name: "alice".to_string(),
+        };
+
+        let mut matcher: RuleMatcher<Holder> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Holder>("age").gte(&18u32))
+            .add_condition(field::<Holder>("age").equals(&30i8))
+            .add_condition(field::<Holder>("age").lt(&30.5f64))
+            .add_condition(field::<Holder>("balance").lt(&0u8))
+            .add_condition(field::<Holder>("balance").gt(&-5.5f64))
+            .add_condition(field::<Holder>("supply").gt(&i128::MAX))
+            .add_condition(field::<Holder>("supply").gt(&u64::MAX))
+            .add_condition(field::<Holder>("ratio").equals(&0.5f64));
+        let result = matcher.evaluate(&holder);
+        assert!(result.is_match());
+        assert_eq!(result.condition_results[0].actual_value.as_deref(), Some("30"));
+        assert_eq!(result.condition_results[0].expected_value.as_deref(), Some("18"));
+
+        // Integers beyond f64 precision are still compared exactly
+        let big = (1u128 << 100) + 1;
+        let almost = (1u128 << 100) as f64;
+        let wrapped = Holder { supply: big, ..holder };
+        let mut matcher: RuleMatcher<Holder> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Holder>("supply").gt(&almost))
+            .add_condition(field::<Holder>("supply").not_equals(&almost))
+            .add_condition(field::<Holder>("age").not_equals(&f64::NAN));
+        assert!(matcher.matches(&wrapped));
+
+        // Values that can't be compared report why
+        let mut matcher: RuleMatcher<Holder> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Holder>("age").equals(&"30"));
+        let result = matcher.evaluate(&wrapped);
+        assert!(!result.is_match());
+        assert_eq!(
+            result.condition_results[0].error,
+            Some(MatchError::TypeMismatch {
+                field: "age".to_string(),
+                expected: "&str".to_string(),
+                actual: "u64".to_string(),
+            })
+        );
+
+        let mut matcher: RuleMatcher<Holder> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Holder>("name").contains(&5u8));
+        matcher.add_condition(field::<Holder>("age").contains(&5u64));
+        let result = matcher.evaluate(&wrapped);
+        assert!(matches!(
+            result.condition_results[0].error,
+            Some(MatchError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            result.condition_results[1].error,
+            Some(MatchError::UnsupportedOperator { .. })
+        ));
+        assert!(result.condition_results.iter().all(|r| !r.passed));
+    }
+
+    #[test]
+    #[cfg(feature = "json_condition")]
+    fn test_json_exact_numbers() {
+        use crate::{condition::Tolerance, matchers::JsonMatcher};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Event {
+            id: u64,
+            timestamp_ns: i128,
+            amount: f64,
+        }
+
+        let event = Event {
+            id: 9_007_199_254_740_993, // 2^53 + 1
+            timestamp_ns: 1_700_000_000_123_456_789,
+            amount: 0.1 + 0.2,
+        };
+
+        let rule = |field: &str, operator: &str, value: serde_json::Value| {
+            let json = serde_json::json!({"rules": [{"field": field, "operator": operator, "value": value}]});
+            JsonMatcher::from_json(&json.to_string()).unwrap()
+        };
+
+        // Integers above 2^53 are not rounded through f64
+        assert!(rule("id", "equals", serde_json::json!(9_007_199_254_740_993u64)).matches(&event));
+        assert!(!rule("id", "equals", serde_json::json!(9_007_199_254_740_992u64)).matches(&event));
+        assert!(rule("id", "greater_than", serde_json::json!(9_007_199_254_740_992u64)).matches(&event));
+        assert!(rule("id", "in", serde_json::json!([9_007_199_254_740_993u64])).matches(&event));
+        assert!(rule("timestamp_ns", "equals", serde_json::json!(1_700_000_000_123_456_789i64)).matches(&event));
+        assert!(!rule("timestamp_ns", "equals", serde_json::json!(1_700_000_000_123_456_788i64)).matches(&event));
+        assert!(rule("timestamp_ns", "between", serde_json::json!([1_700_000_000_123_456_789i64, 1_700_000_000_123_456_790i64])).matches(&event));
+        assert!(!rule("id", "between", serde_json::json!({"min": 9_007_199_254_740_993u64, "inclusive": false})).matches(&event));
+
+        // Floats are equal only when exactly equal, unless a tolerance is set
+        assert!(!rule("amount", "equals", serde_json::json!(0.3)).matches(&event));
+        assert!(rule("amount", "not_equals", serde_json::json!(0.3)).matches(&event));
+        assert!(rule("amount", "equals", serde_json::json!(0.30000000000000004)).matches(&event));
+        let tolerant = rule("amount", "equals", serde_json::json!(0.3)).with_tolerance(Tolerance::absolute(1e-9));
+        assert!(tolerant.matches(&event));
+        let relative = rule("id", "equals", serde_json::json!(9.0e15)).with_tolerance(Tolerance::relative(0.01));
+        assert!(relative.matches(&event));
+        let large = Event { amount: 1e20 + 16384.0, ..event };
+        assert!(!rule("amount", "equals", serde_json::json!(1e20)).matches(&large));
+        assert!(
+            rule("amount", "equals", serde_json::json!(1e20))
+                .with_tolerance(Tolerance::relative(1e-15))
+                .matches(&large)
+        );
+
+        // JSON documents compared against integer operands
+        let doc = serde_json::json!({"id": 9_007_199_254_740_993u64});
+        let id = 9_007_199_254_740_992u64;
+        let mut matcher: RuleMatcher<serde_json::Value> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<serde_json::Value>("id").not_equals(&id));
+        assert!(matcher.matches(&doc));
+    }
+
+    #[test]
+    fn test_condition_tolerance() {
+        use crate::condition::Tolerance;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Reading {
+            price: f64,
+            temperature: f32,
+            count: u32,
+        }
+
+        let reading = Reading {
+            price: 100.004,
+            temperature: 20.9,
+            count: 7,
+        };
+
+        let hundred = 100.0;
+        let mut matcher: RuleMatcher<Reading> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Reading>("price").equals(&hundred));
+        assert!(!matcher.matches(&reading));
+
+        let twenty = 20.0f64;
+        let seven = 7.0f64;
+        let path = ["price"];
+        let mut matcher: RuleMatcher<Reading> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Reading>("price").equals(&hundred).with_tolerance(Tolerance::absolute(0.005)))
+            .add_condition(field::<Reading>("temperature").equals(&twenty).with_tolerance(Tolerance::relative(0.05)))
+            .add_condition(field::<Reading>("temperature").not_equals(&twenty).with_tolerance(Tolerance::relative(0.01)))
+            .add_condition(field::<Reading>("count").equals(&seven).with_tolerance(Tolerance::absolute(0.1)))
+            .add_condition(
+                Condition {
+                    selector: ConditionSelector::Not(Box::new(Condition {
+                        selector: ConditionSelector::FieldPath(&path, &hundred),
+                        operator: ConditionOperator::NotEquals,
+                    })),
+                    operator: ConditionOperator::NotEquals,
+                }
+                .with_tolerance(Tolerance::absolute(0.01)),
+            )
+            .add_condition(Condition::owned_field("price", ConditionOperator::Equals, 100.0).with_tolerance(Tolerance::absolute(0.005)));
+        assert!(matcher.matches(&reading));
+
+        // A nested group inherits the tolerance of the wrapper around it
+        let group = Condition {
+            selector: ConditionSelector::Nested(Box::new(NestedCondition {
+                mode: ConditionMode::AND,
+                rules: vec![field::<Reading>("price").equals(&hundred)],
+                nested: vec![Box::new(NestedCondition {
+                    mode: ConditionMode::OR,
+                    rules: vec![field::<Reading>("count").equals(&seven)],
+                    nested: vec![],
+                })],
+            })),
+            operator: ConditionOperator::Equals,
+        };
+        let mut matcher: RuleMatcher<Reading> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(group.with_tolerance(Tolerance::absolute(0.005)));
+        assert!(matcher.matches(&reading));
+
+        #[cfg(feature = "json_condition")]
+        {
+            use crate::matchers::JsonMatcher;
+
+            let json = r#"{
+                "rules": [
+                    {"field": "price", "operator": "equals", "value": 100.0, "tolerance": 0.005},
+                    {"field": "temperature", "operator": "equals", "value": 21, "relative_tolerance": 0.01}
+                ]
+            }"#;
+            let matcher = JsonMatcher::from_json(json).unwrap();
+            assert!(matcher.matches(&reading));
+            let rule = &matcher.condition().rules[0];
+            assert_eq!(rule.float_tolerance(), Some(Tolerance::absolute(0.005)));
+            assert!(serde_json::to_string(rule).unwrap().contains("\"tolerance\":0.005"));
+            assert!(!serde_json::to_string(&matcher.condition().rules[1]).unwrap().contains("\"tolerance\""));
+
+            // A rule's own tolerance wins over the matcher-wide default
+            let json = r#"{
+                "rules": [
+                    {"field": "price", "operator": "equals", "value": 100.0, "tolerance": 0.001}
+                ]
+            }"#;
+            let matcher = JsonMatcher::from_json(json).unwrap().with_tolerance(Tolerance::absolute(0.01));
+            assert!(!matcher.matches(&reading));
+            let matcher = JsonMatcher::from_dsl("price == 100").unwrap().with_tolerance(Tolerance::absolute(0.01));
+            assert!(matcher.matches(&reading));
+
+            // Tolerances round-trip through the DSL
+            let source = "price == 100 with {tolerance: 0.005} && temperature == 21 with {relative_tolerance: 0.01}";
+            let matcher = JsonMatcher::from_dsl(source).unwrap();
+            assert!(matcher.matches(&reading));
+            assert_eq!(matcher.condition().rules[0].float_tolerance(), Some(Tolerance::absolute(0.005)));
+            assert_eq!(matcher.condition().rules[1].float_tolerance(), Some(Tolerance::relative(0.01)));
+            let printed = matcher.condition().to_string();
+            assert_eq!(printed, source);
+            let reparsed = JsonMatcher::from_dsl(&printed).unwrap();
+            assert_eq!(
+                serde_json::to_value(reparsed.condition()).unwrap(),
+                serde_json::to_value(matcher.condition()).unwrap()
+            );
+            assert!(!JsonMatcher::from_dsl("price == 100").unwrap().matches(&reading));
+
+            assert!(crate::dsl::parse_condition("price == 100 with {tolerence: 0.1}").is_err());
+            assert!(crate::dsl::parse_condition("price == 100 with tolerance").is_err());
+        }
+    }
+
+    #[test]
+    fn test_strings_compared_exactly_by_default() {
+        use crate::condition::StringOptions;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            name: String,
+        }
+
+        let asset = Asset {
+            name: "  Bitcoin Cash ".to_string(),
+        };
+        let plain = "bitcoin cash";
+
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Asset>("name").equals(&plain));
+        assert!(!matcher.matches(&asset));
+
+        // Trimming alone keeps case significant
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Asset>("name").equals(&plain).with_string_options(StringOptions::default().trim()));
+        assert!(!matcher.matches(&asset));
+    }
+
+    #[test]
+    fn test_string_options_apply_to_every_string_operator() {
+        use crate::condition::StringOptions;
+        use std::collections::HashSet;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            name: String,
+            symbol: String,
+        }
+
+        let asset = Asset {
+            name: "  Bitcoin Cash ".to_string(),
+            symbol: "BCH".to_string(),
+        };
+
+        let options = StringOptions::default().case_insensitive().trim();
+        let plain = "bitcoin cash";
+        let prefix = "BITCOIN";
+        let suffix = "cash";
+        let part = "n c";
+        let symbols: HashSet<&str> = ["btc", "bch"].into_iter().collect();
+        let range = "a".to_string().."c".to_string();
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Asset>("name").equals(&plain).with_string_options(options))
+            .add_condition(field::<Asset>("name").starts_with(&prefix).with_string_options(options))
+            .add_condition(field::<Asset>("name").ends_with(&suffix).with_string_options(options))
+            .add_condition(field::<Asset>("name").contains(&part).with_string_options(options))
+            .add_condition(field::<Asset>("symbol").is_in(&symbols).with_string_options(options))
+            .add_condition(field::<Asset>("symbol").between(&range).with_string_options(options));
+        assert!(matcher.matches(&asset));
+    }
+
+    #[test]
+    fn test_string_options_inherited_by_nested_groups() {
+        use crate::condition::StringOptions;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            name: String,
+            symbol: String,
+        }
+
+        let asset = Asset {
+            name: "  Bitcoin Cash ".to_string(),
+            symbol: "BCH".to_string(),
+        };
+
+        let plain = "bitcoin cash";
+        let symbols = vec!["btc", "bch"];
+        let group = Condition {
+            selector: ConditionSelector::Nested(Box::new(NestedCondition {
+                mode: ConditionMode::AND,
+                rules: vec![field::<Asset>("name").equals(&plain)],
+                nested: vec![Box::new(NestedCondition {
+                    mode: ConditionMode::OR,
+                    rules: vec![field::<Asset>("symbol").is_in(&symbols)],
+                    nested: vec![],
+                })],
+            })),
+            operator: ConditionOperator::Equals,
+        };
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(group.with_string_options(StringOptions::default().case_insensitive().trim()));
+        assert!(matcher.matches(&asset));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_string_options_regex() {
+        use crate::condition::StringOptions;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            symbol: String,
+        }
+
+        let asset = Asset {
+            symbol: "BCH".to_string(),
+        };
+
+        let condition = Condition::owned_field("symbol", ConditionOperator::Regex, "^b[a-z]+$".to_string())
+            .with_string_options(StringOptions::default().case_insensitive())
+            .compile()
+            .unwrap();
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(condition);
+        assert!(matcher.matches(&asset));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_string_options_normalization() {
+        use crate::condition::{Normalization, StringOptions};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            name: String,
+            symbol: String,
+        }
+
+        let composed = Asset {
+            name: "Caf\u{e9}".to_string(),
+            symbol: "\u{fb01}".to_string(),
+        };
+        let decomposed = "Cafe\u{301}";
+        let ligature = "fi";
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Asset>("name").equals(&decomposed));
+        assert!(!matcher.matches(&composed));
+
+        let mut matcher: RuleMatcher<Asset> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(
+                field::<Asset>("name")
+                    .equals(&decomposed)
+                    .with_string_options(StringOptions::default().normalize(Normalization::Nfc)),
+            )
+            .add_condition(
+                field::<Asset>("symbol")
+                    .equals(&ligature)
+                    .with_string_options(StringOptions::default().normalize(Normalization::Nfkc)),
+            );
+        assert!(matcher.matches(&composed));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_string_options() {
+        use crate::condition::StringOptions;
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            name: String,
+            symbol: String,
+        }
+
+        let asset = Asset {
+            name: "  Bitcoin Cash ".to_string(),
+            symbol: "BCH".to_string(),
+        };
+
+        let json = r#"{
+            "rules": [
+                {"field": "name", "operator": "starts_with", "value": "bitcoin", "case_insensitive": true, "trim": true},
+                {"field": "name", "operator": "equals", "value": "BITCOIN CASH", "case_insensitive": true, "trim": true},
+                {"field": "symbol", "operator": "in", "value": ["btc", "bch"], "case_insensitive": true},
+                {"field": "symbol", "operator": "between", "value": ["a", "c"], "case_insensitive": true}
+            ]
+        }"#;
+        let matcher = JsonMatcher::from_json(json).unwrap();
+        assert!(matcher.matches(&asset));
+        assert_eq!(
+            matcher.condition().rules[0].string_options(),
+            StringOptions::default().case_insensitive().trim()
+        );
+        assert!(!serde_json::to_string(&matcher.condition().rules[2]).unwrap().contains("\"trim\""));
+
+        let json = r#"{"rules": [{"field": "name", "operator": "equals", "value": "bitcoin cash"}]}"#;
+        assert!(!JsonMatcher::from_json(json).unwrap().matches(&asset));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_dsl_string_options() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            name: String,
+            symbol: String,
+        }
+
+        let asset = Asset {
+            name: "  Bitcoin Cash ".to_string(),
+            symbol: "BCH".to_string(),
+        };
+
+        let source = r#"name starts_with "bitcoin" with {case_insensitive: true, trim: true} && symbol in ["btc","bch"] with {case_insensitive: true}"#;
+        assert!(JsonMatcher::from_dsl(source).unwrap().matches(&asset));
+        assert!(!JsonMatcher::from_dsl(r#"name starts_with "bitcoin""#).unwrap().matches(&asset));
+    }
+
+    #[cfg(all(feature = "json_condition", feature = "regex"))]
+    #[test]
+    fn test_json_string_options_regex() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            symbol: String,
+        }
+
+        let asset = Asset {
+            symbol: "BCH".to_string(),
+        };
+
+        let json = r#"{"rules": [{"field": "symbol", "operator": "regex", "value": "^b[a-z]+$", "case_insensitive": true}]}"#;
+        assert!(JsonMatcher::from_json(json).unwrap().matches(&asset));
+    }
+
+    #[cfg(all(feature = "json_condition", feature = "unicode"))]
+    #[test]
+    fn test_json_string_options_normalization() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Asset {
+            symbol: String,
+        }
+
+        let ligature = Asset {
+            symbol: "\u{fb01}".to_string(),
+        };
+
+        let json = r#"{"rules": [{"field": "symbol", "operator": "equals", "value": "FI", "normalize": "nfkc", "case_insensitive": true}]}"#;
+        assert!(JsonMatcher::from_json(json).unwrap().matches(&ligature));
+
+        let source = r#"symbol == "FI" with {case_insensitive: true, normalize: "nfkc"}"#;
+        assert!(JsonMatcher::from_dsl(source).unwrap().matches(&ligature));
+    }
+
+    #[test]
+    fn test_collection_operators() {
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Order {
+            amount: f64,
+            status: String,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Customer {
+            tags: Vec<String>,
+            scores: Vec<u32>,
+            #[matchable(nested)]
+            orders: Vec<Order>,
+        }
+
+        let customer = Customer {
+            tags: vec!["vip".to_string(), "beta".to_string()],
+            scores: vec![3, 7],
+            orders: vec![
+                Order { amount: 40.0, status: "paid".to_string() },
+                Order { amount: 150.0, status: "paid".to_string() },
+            ],
+        };
+
+        let any_tag = vec!["admin", "vip"];
+        let all_tags = vec!["vip", "beta"];
+        let missing_tags = vec!["vip", "admin"];
+        let scores: &[u64] = &[7];
+        let mut matcher: RuleMatcher<Customer> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Customer>("tags").contains_any(&any_tag))
+            .add_condition(field::<Customer>("tags").contains_all(&all_tags))
+            .add_condition(field::<Customer>("scores").contains_all(&scores));
+        assert!(matcher.matches(&customer));
+
+        let mut matcher: RuleMatcher<Customer> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(field::<Customer>("tags").contains_all(&missing_tags));
+        assert!(!matcher.matches(&customer));
+
+        // A scalar operand is not a collection
+        let scalar = "vip";
+        let result = field::<Customer>("tags").contains_any(&scalar).test_detailed(&customer);
+        assert!(!result.passed);
+        assert!(result.error.is_some());
+
+        #[cfg(feature = "json_condition")]
+        {
+            use crate::condition::JsonNestedCondition;
+            use crate::error::MatchError;
+            use crate::matchers::JsonMatcher;
+            use crate::schema::{FieldKind, Schema};
+
+            let large: JsonNestedCondition =
+                serde_json::from_str(r#"{"rules": [{"field": "amount", "operator": "greater_than", "value": 100}]}"#)
+                    .unwrap();
+            let paid: JsonNestedCondition =
+                serde_json::from_str(r#"{"rules": [{"field": "status", "operator": "equals", "value": "paid"}]}"#)
+                    .unwrap();
+
+            let result = field::<Customer>("orders").any_element(&large).test_detailed(&customer);
+            assert!(result.passed);
+            assert_eq!(result.actual_value.as_deref(), Some("2 elements"));
+            assert!(field::<Customer>("orders").all_elements(&paid).test_detailed(&customer).passed);
+            assert!(!field::<Customer>("orders").all_elements(&large).test_detailed(&customer).passed);
+
+            let result = field::<Customer>("scores").any_element(&large).test_detailed(&customer);
+            assert!(matches!(result.error, Some(MatchError::TypeMismatch { .. })));
+            let result = Condition::<Customer> {
+                selector: ConditionSelector::FieldValue("orders", &scalar),
+                operator: ConditionOperator::AnyElement,
+            }
+            .test_detailed(&customer);
+            assert!(matches!(result.error, Some(MatchError::InvalidOperand { .. })));
+
+            let json = r#"{
+                "mode": "AND",
+                "rules": [
+                    {"field": "tags", "operator": "contains_any", "value": ["admin", "vip"]},
+                    {"field": "scores", "operator": "contains_all", "value": [3, 7]},
+                    {"field": "orders", "operator": "any_element", "value": {
+                        "mode": "AND",
+                        "rules": [
+                            {"field": "amount", "operator": "greater_than", "value": 100},
+                            {"field": "status", "operator": "equals", "value": "paid"}
+                        ]
+                    }}
+                ]
+            }"#;
+            let matcher = JsonMatcher::from_json(json).unwrap();
+            assert!(matcher.matches(&customer));
+
+            let json = r#"{"rules": [{"field": "orders", "operator": "all_elements", "value": {"rules": [{"field": "amount", "operator": "greater_than", "value": 100}]}}]}"#;
+            assert!(!JsonMatcher::from_json(json).unwrap().matches(&customer));
+
+            // JSON documents: arrays of objects and scalars
+            let payload = serde_json::json!({
+                "labels": ["urgent", "billing"],
+                "items": [{"sku": "A-1", "qty": 2}, {"sku": "B-7", "qty": 0}]
+            });
+            let matcher = JsonMatcher::from_dsl(
+                r#"labels contains_all ["billing"] && items any_element {rules: [{field: "qty", operator: "equals", value: 0}]}"#,
+            )
+            .unwrap();
+            assert!(matcher.matches(&payload));
+            let matcher = JsonMatcher::from_dsl(
+                r#"items all_elements {rules: [{field: "qty", operator: "greater_than", value: 0}]}"#,
+            )
+            .unwrap();
+            assert!(!matcher.matches(&payload));
+
+            // The per-element condition is checked up front
+            let invalid = r#"{"rules": [{"field": "orders", "operator": "any_element", "value": 5}]}"#;
+            assert!(matches!(
+                JsonMatcher::from_json(invalid).unwrap().compile(),
+                Err(MatchError::InvalidOperand { .. })
+            ));
+
+            let schema = Schema::new()
+                .field("tags", FieldKind::List(Box::new(FieldKind::String)))
+                .field(
+                    "orders",
+                    FieldKind::List(Box::new(FieldKind::Object(
+                        Schema::new().field("amount", FieldKind::Number),
+                    ))),
+                );
+            let valid = JsonMatcher::from_dsl(
+                r#"tags contains_any ["vip"] && orders any_element {rules: [{field: "amount", operator: "greater_than", value: 100}]}"#,
+            )
+            .unwrap();
+            assert_eq!(valid.validate(&schema), Ok(()));
+            let invalid = JsonMatcher::from_dsl(
+                r#"tags contains_any [1] || orders all_elements {rules: [{field: "amonut", operator: "equals", value: 1}]}"#,
+            )
+            .unwrap();
+            assert_eq!(invalid.validate(&schema).unwrap_err().len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_field_to_field_comparisons() {
+        use crate::error::MatchError;
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Band {
+            upper: f64,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Ticker {
+            symbol: String,
+            bid_price: f64,
+            ask_price: f64,
+            current_price: f64,
+            sma_50: u32,
+            watchlist: Vec<String>,
+            #[matchable(nested)]
+            band: Band,
+        }
+
+        let ticker = Ticker {
+            symbol: "BTC".to_string(),
+            bid_price: 99.5,
+            ask_price: 100.0,
+            current_price: 104.0,
+            sma_50: 100,
+            watchlist: vec!["ETH".to_string(), "BTC".to_string()],
+            band: Band { upper: 110.0 },
+        };
+
+        let mut matcher: RuleMatcher<Ticker> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Ticker>("bid_price").compare_field(ConditionOperator::LessThan, "ask_price"))
+            .add_condition(field::<Ticker>("current_price").compare_field(ConditionOperator::GreaterThan, "sma_50"))
+            .add_condition(field::<Ticker>("current_price").compare_field(ConditionOperator::LessThan, "band.upper"))
+            .add_condition(field::<Ticker>("symbol").compare_field(ConditionOperator::In, "watchlist"));
+        assert!(matcher.matches(&ticker));
+
+        let result = Condition::<Ticker> {
+            selector: ConditionSelector::FieldToField("ask_price", "bid_price"),
+            operator: ConditionOperator::LessThanOrEqual,
+        }
+        .test_detailed(&ticker);
+        assert!(!result.passed);
+        assert_eq!(result.actual_value.as_deref(), Some("100"));
+        assert_eq!(result.expected_value.as_deref(), Some("99.5"));
+
+        let result = field::<Ticker>("bid_price")
+            .compare_field(ConditionOperator::LessThan, "sma_200")
+            .test_detailed(&ticker);
+        assert!(matches!(result.error, Some(MatchError::FieldNotFound { field, .. }) if field == "sma_200"));
+
+        #[cfg(feature = "json_condition")]
+        {
+            use crate::error::ValidationError;
+            use crate::matchers::JsonMatcher;
+            use crate::schema::{FieldKind, Schema};
+
+            let json = r#"{
+                "mode": "AND",
+                "rules": [
+                    {"field": "bid_price", "operator": "less_than", "value": {"$field": "ask_price"}},
+                    {"field": "current_price", "operator": "greater_than", "value": {"$field": "sma_50"}},
+                    {"field": "band.upper", "operator": "greater_than", "value": {"$field": "current_price"}},
+                    {"field": "symbol", "operator": "in", "value": {"$field": "watchlist"}}
+                ]
+            }"#;
+            let matcher = JsonMatcher::from_json(json).unwrap();
+            assert!(matcher.matches(&ticker));
+
+            let matcher = JsonMatcher::from_dsl(r#"current_price < {"$field": "sma_50"}"#).unwrap();
+            assert!(!matcher.matches(&ticker));
+            let result = matcher.evaluate(&ticker);
+            assert!(result.details[0].error.is_none());
+
+            let matcher = JsonMatcher::from_dsl(r#"current_price < {"$field": "sma_200"}"#).unwrap();
+            let result = matcher.evaluate(&ticker);
+            assert_eq!(result.details[0].error.as_deref(), Some("Field 'sma_200' not found"));
+
+            // Objects with other keys are still literal operands
+            let json = r#"{"rules": [{"field": "current_price", "operator": "equals", "value": {"$field": "sma_50", "x": 1}}]}"#;
+            assert!(JsonMatcher::from_json(json).unwrap().condition().rules[0].field_reference().is_none());
+
+            // Fields of JSON documents
+            let payload = serde_json::json!({"quote": {"bid": 10.5, "ask": 10.75}, "last": 10.75});
+            let matcher = JsonMatcher::from_dsl(
+                r#"quote.bid < {"$field": "quote.ask"} && last == {"$field": "quote.ask"}"#,
+            )
+            .unwrap();
+            assert!(matcher.matches(&payload));
+
+            let schema = Schema::new()
+                .field("symbol", FieldKind::String)
+                .field("bid_price", FieldKind::Number)
+                .field("ask_price", FieldKind::Number)
+                .field("watchlist", FieldKind::List(Box::new(FieldKind::String)));
+            let valid = JsonMatcher::from_dsl(
+                r#"bid_price < {"$field": "ask_price"} && symbol in {"$field": "watchlist"}"#,
+            )
+            .unwrap();
+            assert_eq!(valid.validate(&schema), Ok(()));
+            let invalid = JsonMatcher::from_dsl(
+                r#"bid_price < {"$field": "ask"} || symbol == {"$field": "bid_price"}"#,
+            )
+            .unwrap();
+            let errors = invalid.validate(&schema).unwrap_err();
+            assert_eq!(errors[0].to_string(), "Unknown field 'ask'");
+            assert!(matches!(&errors[1], ValidationError::InvalidValue { field, .. } if field == "symbol"));
+        }
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_expression_fields() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Range {
+            high: f64,
+            low: f64,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Market {
+            price: f64,
+            open: f64,
+            price_change_24h: f64,
+            volume: u64,
+            avg_volume: u32,
+            #[matchable(nested)]
+            range: Range,
+        }
+
+        let market = Market {
+            price: 100.0,
+            open: 96.0,
+            price_change_24h: 8.0,
+            volume: 4_000,
+            avg_volume: 1_000,
+            range: Range { high: 104.0, low: 90.0 },
+        };
+
+        let json = r#"{
+            "mode": "AND",
+            "rules": [
+                {"field": {"$div": [{"$field": "price_change_24h"}, {"$field": "price"}]}, "operator": "greater_than", "value": 0.05},
+                {"field": "volume", "operator": "greater_than", "value": {"$mul": [{"$field": "avg_volume"}, 3]}},
+                {"field": {"$abs": {"$sub": [{"$field": "open"}, {"$field": "price"}]}}, "operator": "less_than", "value": {"$sub": [{"$field": "range.high"}, {"$field": "range.low"}]}},
+                {"field": {"$max": [{"$field": "open"}, {"$field": "price"}]}, "operator": "equals", "value": 100},
+                {"field": {"$add": [{"$field": "range.low"}, 10]}, "operator": "between", "value": [99, 101]},
+                {"field": {"$min": [{"$field": "open"}, {"$field": "range.low"}]}, "operator": "less_than", "value": {"$field": "open"}}
+            ]
+        }"#;
+        let matcher = JsonMatcher::from_json(json).unwrap();
+        let result = matcher.evaluate(&market);
+        assert!(result.matched, "{:?}", result.details);
+        assert_eq!(result.details[0].field, "price_change_24h / price");
+        assert_eq!(matcher.condition().rules[1].field, "volume");
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_expression_errors() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Market {
+            symbol: String,
+            price: f64,
+            open: f64,
+        }
+
+        let market = Market {
+            symbol: "SOL".to_string(),
+            price: 100.0,
+            open: 96.0,
+        };
+
+        let json = r#"{"rules": [
+            {"field": {"$div": [{"$field": "price"}, {"$sub": [{"$field": "open"}, 96]}]}, "operator": "greater_than", "value": 1},
+            {"field": "price", "operator": "less_than", "value": {"$mul": [{"$field": "avg_price"}, 2]}},
+            {"field": {"$add": [{"$field": "symbol"}, 1]}, "operator": "greater_than", "value": 1}
+        ]}"#;
+        let result = JsonMatcher::from_json(json).unwrap().evaluate(&market);
+        assert!(!result.matched);
+        assert_eq!(result.details[0].error.as_deref(), Some("Division by zero in 'price / (open - 96)'"));
+        assert!(result.details[1].error.as_deref().unwrap().contains("'avg_price' not found"));
+        assert!(result.details[2].error.as_deref().unwrap().contains("expected 'number'"));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_expression_empty_operands() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Market {
+            price: f64,
+        }
+
+        let market = Market { price: 100.0 };
+
+        let json = r#"{"rules": [{"field": {"$min": []}, "operator": "less_than", "value": 1}]}"#;
+        assert!(JsonMatcher::from_json(json).is_err());
+
+        let json = r#"{"rules": [{"field": "price", "operator": "less_than", "value": {"$max": []}}]}"#;
+        let matcher = JsonMatcher::from_json(json).unwrap();
+        let result = matcher.evaluate(&market);
+        assert!(!result.matched);
+        assert!(result.details[0].error.as_deref().unwrap().contains("'$max'"));
+        assert!(matches!(
+            matcher.compile(),
+            Err(crate::MatchError::InvalidOperand { operator, .. }) if operator == "$max"
+        ));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_dsl_expression_fields() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Range {
+            low: f64,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Market {
+            price: f64,
+            open: f64,
+            price_change_24h: f64,
+            #[matchable(nested)]
+            range: Range,
+        }
+
+        let market = Market {
+            price: 100.0,
+            open: 96.0,
+            price_change_24h: 8.0,
+            range: Range { low: 90.0 },
+        };
+
+        let source = "price_change_24h / price > 0.05 && abs(open - price) < 5 && max(open, price) == 100 && range.low + 10 + 0 between [99,101] && ((range.low - 5) * 2) / 4 <= 42.5 && (open + price) / 2 == 98";
+        let result = JsonMatcher::from_dsl(source).unwrap().evaluate(&market);
+        assert!(result.matched, "{:?}", result.details);
+
+        let matcher = JsonMatcher::from_dsl("open -1 == 95 && open-1 == 95 && open + price * 2 - -4 > 0").unwrap();
+        assert!(matcher.matches(&market));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_document_expression_fields() {
+        use crate::matchers::JsonMatcher;
+
+        let payload = serde_json::json!({"bid": 99.0, "ask": 101.0, "mid": 100.0});
+        let matcher = JsonMatcher::from_json(
+            r#"{"rules": [{"field": {"$div": [{"$add": [{"$field": "bid"}, {"$field": "ask"}]}, 2]}, "operator": "equals", "value": {"$field": "mid"}}]}"#,
+        )
+        .unwrap();
+        assert!(matcher.matches(&payload));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_expression_schema_validation() {
+        use crate::error::ValidationError;
+        use crate::matchers::JsonMatcher;
+        use crate::schema::{FieldKind, Schema};
+
+        let schema = Schema::new()
+            .field("symbol", FieldKind::String)
+            .field("price", FieldKind::Number)
+            .field("volume", FieldKind::Number)
+            .field("avg_volume", FieldKind::Number);
+        let valid = JsonMatcher::from_json(
+            r#"{"rules": [{"field": "volume", "operator": "greater_than", "value": {"$mul": [{"$field": "avg_volume"}, 3]}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(valid.validate(&schema), Ok(()));
+
+        let invalid = JsonMatcher::from_json(
+            r#"{"mode": "OR", "rules": [
+                {"field": {"$mul": [{"$field": "volume"}, {"$field": "volme"}]}, "operator": "greater_than", "value": 1},
+                {"field": "price", "operator": "less_than", "value": {"$abs": {"$field": "symbol"}}},
+                {"field": "symbol", "operator": "equals", "value": {"$add": [{"$field": "price"}, 1]}}
+            ]}"#,
+        )
+        .unwrap();
+        let errors = invalid.validate(&schema).unwrap_err();
+        assert_eq!(errors[0].to_string(), "Unknown field 'volme'");
+        assert!(matches!(&errors[1], ValidationError::UnsupportedOperator { field, .. } if field == "symbol"));
+        assert!(matches!(&errors[2], ValidationError::InvalidValue { field, .. } if field == "symbol"));
+    }
+
+    #[test]
+    fn test_derived_field_is_none() {
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Profile {
+            nickname: Option<String>,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            name: String,
+            email: Option<String>,
+            phone: Option<String>,
+            #[matchable(nested)]
+            profile: Profile,
+            #[matchable(nested)]
+            backup: Option<Profile>,
+        }
+
+        let account = Account {
+            name: "Ada".to_string(),
+            email: None,
+            phone: Some("555".to_string()),
+            profile: Profile { nickname: None },
+            backup: None,
+        };
+
+        assert_eq!(account.field_is_none(&["email"]), Some(true));
+        assert_eq!(account.field_is_none(&["phone"]), Some(false));
+        assert_eq!(account.field_is_none(&["name"]), Some(false));
+        assert_eq!(account.field_is_none(&["coupon"]), None);
+        assert_eq!(account.field_is_none(&["profile", "nickname"]), Some(true));
+        assert_eq!(account.field_is_none(&["backup"]), Some(true));
+        assert_eq!(account.field_is_none(&["backup", "nickname"]), None);
+    }
+
+    #[test]
+    fn test_presence_operators() {
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Profile {
+            nickname: Option<String>,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            email: Option<String>,
+            phone: Option<String>,
+            #[matchable(nested)]
+            profile: Profile,
+            #[matchable(nested)]
+            backup: Option<Profile>,
+        }
+
+        let account = Account {
+            email: None,
+            phone: Some("555".to_string()),
+            profile: Profile { nickname: None },
+            backup: None,
+        };
+
+        let mut matcher: RuleMatcher<Account> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Account>("email").is_none())
+            .add_condition(field::<Account>("email").exists())
+            .add_condition(field::<Account>("phone").is_some())
+            .add_condition(field::<Account>("coupon").not_exists())
+            .add_condition(field::<Account>("profile.nickname").is_none())
+            .add_condition(field::<Account>("backup.nickname").not_exists());
+        assert!(matcher.matches(&account));
+
+        let result = Condition::<Account> {
+            selector: ConditionSelector::FieldPath(&["profile", "nickname"], &()),
+            operator: ConditionOperator::IsNone,
+        }
+        .test_detailed(&account);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_presence_of_missing_field() {
+        use crate::error::MatchError;
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            email: Option<String>,
+        }
+
+        let account = Account { email: None };
+
+        // A missing field is an error for IsNone/IsSome, not a None
+        let result = field::<Account>("coupon").is_none().test_detailed(&account);
+        assert!(!result.passed);
+        assert!(matches!(result.error, Some(MatchError::FieldNotFound { field, .. }) if field == "coupon"));
+
+        let result = field::<Account>("email").is_some().test_detailed(&account);
+        assert!(!result.passed && result.error.is_none());
+        assert_eq!(result.actual_value.as_deref(), Some("none"));
+    }
+
+    #[test]
+    fn test_presence_of_enum_variant_fields() {
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        enum Payment {
+            Card { last4: Option<String> },
+            Cash,
+        }
+
+        let card = Payment::Card { last4: None };
+        assert!(field::<Payment>("last4").is_none().test(&card));
+        assert!(field::<Payment>("last4").not_exists().test(&Payment::Cash));
+    }
+
+    #[test]
+    fn test_compare_none_field() {
+        use crate::error::MatchError;
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Profile {
+            nickname: Option<String>,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            email: Option<String>,
+            #[matchable(nested)]
+            profile: Profile,
+        }
+
+        let account = Account {
+            email: None,
+            profile: Profile { nickname: None },
+        };
+
+        // Comparing a None field doesn't match, but isn't an error either
+        let expected = "ada@example.com".to_string();
+        let result = field::<Account>("email").equals(&expected).test_detailed(&account);
+        assert!(!result.passed && result.error.is_none());
+        assert_eq!(result.actual_value.as_deref(), Some("none"));
+
+        let result = field::<Account>("email").not_equals(&expected).test_detailed(&account);
+        assert!(!result.passed && result.error.is_none());
+
+        let result = Condition::<Account> {
+            selector: ConditionSelector::FieldPath(&["profile", "nickname"], &expected),
+            operator: ConditionOperator::Equals,
+        }
+        .test_detailed(&account);
+        assert!(!result.passed && result.error.is_none());
+
+        let result = field::<Account>("coupon").equals(&expected).test_detailed(&account);
+        assert!(matches!(result.error, Some(MatchError::FieldNotFound { field, .. }) if field == "coupon"));
+    }
+
+    #[test]
+    fn test_manual_field_is_none() {
+        use crate::traits::Predicate;
+
+        // Manual implementations report None fields by overriding field_is_none
+        #[derive(PartialEq, Debug)]
+        struct Contact {
+            email: Option<String>,
+        }
+
+        impl Matchable for Contact {
+            fn get_field(&self, field: &str) -> Option<&dyn std::any::Any> {
+                match field {
+                    "email" => self.email.as_ref().map(|e| e as &dyn std::any::Any),
+                    _ => None,
+                }
+            }
+
+            fn field_is_none(&self, path: &[&str]) -> Option<bool> {
+                match path {
+                    ["email"] => Some(self.email.is_none()),
+                    _ => self.get_field_path_value(path).map(|v| v.is_null()),
+                }
+            }
+        }
+
+        let contact = Contact { email: None };
+        let mut matcher: RuleMatcher<Contact> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Contact>("email").is_none())
+            .add_condition(field::<Contact>("email").exists())
+            .add_condition(field::<Contact>("phone").not_exists());
+        assert!(matcher.matches(&contact));
+
+        let expected = "ada@example.com".to_string();
+        let result = field::<Contact>("email").equals(&expected).test_detailed(&contact);
+        assert!(!result.passed && result.error.is_none());
+        assert!(field::<Contact>("email").is_some().test(&Contact { email: Some(expected.clone()) }));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_presence_operators() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            email: Option<String>,
+            phone: Option<String>,
+        }
+
+        let account = Account {
+            email: None,
+            phone: Some("555".to_string()),
+        };
+
+        let json = r#"{
+            "mode": "AND",
+            "rules": [
+                {"field": "email", "operator": "is_none"},
+                {"field": "email", "operator": "exists"},
+                {"field": "phone", "operator": "is_some"},
+                {"field": "coupon", "operator": "not_exists"}
+            ]
+        }"#;
+        assert!(JsonMatcher::from_json(json).unwrap().matches(&account));
+
+        let matcher = JsonMatcher::from_dsl("email is_none && coupon not_exists").unwrap();
+        assert!(matcher.matches(&account));
+
+        let result = JsonMatcher::from_dsl("coupon is_some").unwrap().evaluate(&account);
+        assert!(!result.matched);
+        assert_eq!(result.details[0].error.as_deref(), Some("Field 'coupon' not found"));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_compare_none_field() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Profile {
+            nickname: Option<String>,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Account {
+            name: String,
+            email: Option<String>,
+            #[matchable(nested)]
+            profile: Profile,
+        }
+
+        let account = Account {
+            name: "Ada".to_string(),
+            email: None,
+            profile: Profile { nickname: None },
+        };
+
+        let matcher = JsonMatcher::from_dsl(
+            r#"email == "ada@example.com" || email != {"$field": "name"} || profile.nickname starts_with "A""#,
+        )
+        .unwrap();
+        let result = matcher.evaluate(&account);
+        assert!(!result.matched);
+        assert!(result.details.iter().all(|d| d.error.is_none()), "{:?}", result.details);
+        assert_eq!(result.details[0].actual, Some(serde_json::Value::Null));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_document_presence() {
+        use crate::matchers::JsonMatcher;
+
+        // JSON null is None, a missing key doesn't exist
+        let doc = serde_json::json!({"user": {"email": null, "name": "Ada"}});
+        let matcher = JsonMatcher::from_dsl(
+            "user.email is_none && user.email exists && user.name is_some && user.phone not_exists",
+        )
+        .unwrap();
+        let result = matcher.evaluate(&doc);
+        assert!(result.matched, "{:?}", result.details);
+        assert_eq!(result.details[0].actual, Some(serde_json::json!("none")));
+        assert_eq!(result.details[3].actual, Some(serde_json::json!("missing")));
+    }
+
+    #[test]
+    fn test_emptiness_operators() {
+        use std::collections::HashMap;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Item {
+            sku: String,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Cart {
+            owner: String,
+            note: String,
+            tags: Vec<String>,
+            quantities: HashMap<String, u32>,
+            coupon: Option<String>,
+            promo: Option<String>,
+            #[matchable(nested)]
+            items: Vec<Item>,
+        }
+
+        let cart = Cart {
+            owner: "ada".to_string(),
+            note: String::new(),
+            tags: vec![],
+            quantities: HashMap::from([("apple".to_string(), 2)]),
+            coupon: None,
+            promo: Some("SPRING".to_string()),
+            items: vec![],
+        };
+
+        let mut matcher: RuleMatcher<Cart> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Cart>("owner").is_not_empty())
+            .add_condition(field::<Cart>("note").is_empty())
+            .add_condition(field::<Cart>("tags").is_empty())
+            .add_condition(field::<Cart>("quantities").is_not_empty())
+            .add_condition(field::<Cart>("coupon").is_empty())
+            .add_condition(field::<Cart>("promo").is_not_empty())
+            .add_condition(field::<Cart>("items").is_empty())
+            .add_condition(field::<Cart>("items.0").not_exists());
+        assert!(matcher.matches(&cart));
+    }
+
+    #[test]
+    fn test_emptiness_result() {
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Cart {
+            tags: Vec<String>,
+        }
+
+        let cart = Cart { tags: vec![] };
+
+        let result = field::<Cart>("tags").is_not_empty().test_detailed(&cart);
+        assert!(!result.passed && result.error.is_none());
+        assert_eq!(result.actual_value.as_deref(), Some("empty"));
+    }
+
+    #[test]
+    fn test_emptiness_of_non_scalar_collections() {
+        use std::collections::{HashSet, VecDeque};
+
+        #[derive(PartialEq, Debug)]
+        struct Receipt {
+            number: u32,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Cart {
+            coupon: Option<String>,
+            total: f64,
+            receipts: Vec<Receipt>,
+            history: VecDeque<Receipt>,
+            flags: Option<HashSet<char>>,
+        }
+
+        let cart = Cart {
+            coupon: None,
+            total: 12.5,
+            receipts: vec![Receipt { number: 1 }],
+            history: VecDeque::new(),
+            flags: Some(HashSet::new()),
+        };
+
+        // Collections of any element type, decided by the derived field_is_empty
+        assert_eq!(cart.field_is_empty(&["receipts"]), Some(false));
+        assert_eq!(cart.field_is_empty(&["history"]), Some(true));
+        assert_eq!(cart.field_is_empty(&["coupon"]), Some(true));
+        assert_eq!(cart.field_is_empty(&["total"]), None);
+
+        let mut matcher: RuleMatcher<Cart> = RuleMatcher::new(ConditionMode::AND);
+        matcher
+            .add_condition(field::<Cart>("receipts").is_not_empty())
+            .add_condition(field::<Cart>("history").is_empty())
+            .add_condition(field::<Cart>("flags").is_empty());
+        assert!(matcher.matches(&cart));
+    }
+
+    #[test]
+    fn test_emptiness_errors() {
+        use crate::error::MatchError;
+        use crate::traits::Predicate;
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Cart {
+            total: f64,
+        }
+
+        let cart = Cart { total: 12.5 };
+
+        let result = field::<Cart>("total").is_empty().test_detailed(&cart);
+        assert!(matches!(result.error, Some(MatchError::TypeMismatch { field, .. }) if field == "total"));
+
+        let result = field::<Cart>("discount").is_empty().test_detailed(&cart);
+        assert!(matches!(result.error, Some(MatchError::FieldNotFound { field, .. }) if field == "discount"));
+    }
+
+    #[test]
+    fn test_value_emptiness() {
+        // Matchable::is_empty on the value itself
+        let mut matcher: RuleMatcher<Vec<u32>> = RuleMatcher::new(ConditionMode::AND);
+        matcher.add_condition(Condition {
+            selector: ConditionSelector::Value(vec![]),
+            operator: ConditionOperator::IsEmpty,
+        });
+        assert!(matcher.matches(&Vec::new()));
+        assert!(!matcher.matches(&vec![1]));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_emptiness_operators() {
+        use crate::matchers::JsonMatcher;
+
+        #[derive(PartialEq, Debug)]
+        struct Receipt {
+            number: u32,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Cart {
+            owner: String,
+            tags: Vec<String>,
+            coupon: Option<String>,
+            receipts: Vec<Receipt>,
+        }
+
+        let cart = Cart {
+            owner: "ada".to_string(),
+            tags: vec![],
+            coupon: None,
+            receipts: vec![Receipt { number: 1 }],
+        };
+
+        let json = r#"{
+            "mode": "AND",
+            "rules": [
+                {"field": "owner", "operator": "is_not_empty"},
+                {"field": "tags", "operator": "is_empty"},
+                {"field": "coupon", "operator": "is_empty"},
+                {"field": "receipts", "operator": "is_not_empty"}
+            ]
+        }"#;
+        assert!(JsonMatcher::from_json(json).unwrap().matches(&cart));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_document_emptiness() {
+        use crate::matchers::JsonMatcher;
+
+        let doc = serde_json::json!({
+            "tags": [],
+            "meta": {},
+            "name": "",
+            "middle": null,
+            "orders": [{"id": 1}]
+        });
+        let matcher = JsonMatcher::from_dsl(
+            "tags is_empty && meta is_empty && name is_empty && middle is_empty && orders is_not_empty",
+        )
+        .unwrap();
+        let result = matcher.evaluate(&doc);
+        assert!(result.matched, "{:?}", result.details);
+        assert_eq!(result.details[4].actual, Some(serde_json::json!("not empty")));
+    }
+
+    #[cfg(feature = "json_condition")]
+    #[test]
+    fn test_json_document_emptiness_errors() {
+        use crate::matchers::JsonMatcher;
+
+        let doc = serde_json::json!({"count": 3});
+        let result = JsonMatcher::from_dsl("count is_empty || missing is_not_empty")
+            .unwrap()
+            .evaluate(&doc);
+        assert!(!result.matched);
+        assert_eq!(result.details[0].error.as_deref(), Some("Field 'count' is not a string or collection"));
+        assert_eq!(result.details[1].error.as_deref(), Some("Field 'missing' not found"));
+    }
+
+    #[test]
+    fn test_recursive_schema() {
+        use crate::schema::{FieldKind, Schema};
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Node {
+            label: String,
+            #[matchable(nested)]
+            children: Vec<Node>,
+            #[matchable(nested)]
+            owner: Option<Box<Owner>>,
+        }
+
+        #[derive(MatchableDerive, PartialEq, Debug)]
+        struct Owner {
+            name: String,
+            #[matchable(nested)]
+            root: Option<Box<Node>>,
+        }
+
+        let schema = Schema::of::<Node>();
+        assert_eq!(schema.resolve("label").unwrap().kind, FieldKind::String);
+        assert_eq!(schema.resolve("owner.name").unwrap().kind, FieldKind::String);
+        assert_eq!(schema.resolve("children").unwrap().kind, FieldKind::List(Box::new(FieldKind::Any)));
+        assert_eq!(schema.resolve("owner.root.children.0.label").unwrap().kind, FieldKind::Any);
+
+        assert_eq!(Node::field_info(), Node::field_info());
+        assert_ne!(Node::field_info()[1], Owner::field_info()[1]);
+    }
+
     #[test]
     fn test_batch_operations() {
         use crate::batch;